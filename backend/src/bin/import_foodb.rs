@@ -0,0 +1,35 @@
+//! Adds the foods of a FooDB dump to the data file as ingredients:
+//!
+//! ```text
+//! DATA_FILE=whats-cooking.json cargo run --bin import_foodb -- Food.csv
+//! ```
+//!
+//! Stop the server first, it would overwrite the imported ingredients on its
+//! next change.
+use std::env;
+use whats_cooking_backend::foodb::{read_foods, Food};
+use whats_cooking_backend::store::DEFAULT_DATA_FILE;
+use whats_cooking_backend::Store;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dump = env::args()
+        .nth(1)
+        .ok_or("usage: import_foodb <Food.csv or Food.json>")?;
+    let data_file = env::var("DATA_FILE").unwrap_or_else(|_| DEFAULT_DATA_FILE.to_string());
+
+    let foods = read_foods(&dump)?;
+    let total = foods.len();
+    let ingredients: Vec<_> = foods
+        .into_iter()
+        .filter_map(Food::into_ingredient)
+        .collect();
+    let incomplete = total - ingredients.len();
+
+    let store = Store::open(&data_file)?;
+    let summary = store.import_foodb(ingredients)?;
+    println!(
+        "read {} foods from {} into {}: {} added, {} linked to existing ingredients, {} duplicates, {} without a name or food group",
+        total, dump, data_file, summary.created, summary.linked, summary.duplicates, incomplete
+    );
+    Ok(())
+}
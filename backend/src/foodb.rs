@@ -0,0 +1,147 @@
+//! Reading the foods of a FooDB dump, the `Food.csv` or `Food.json` file
+//! from <https://foodb.ca/downloads>.
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use whats_cooking_types::{csv::parse_csv, taxonomy::Taxonomy, NewIngredient};
+
+/// The columns of a FooDB food the ingredients are made of. Others are ignored.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Food {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub name_scientific: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub food_group: Option<String>,
+    #[serde(default)]
+    pub food_subgroup: Option<String>,
+}
+
+fn optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+impl Food {
+    /// The ingredient for this food with its FooDB id, or `None` when it has
+    /// no name or food group, which every ingredient needs.
+    pub fn into_ingredient(self) -> Option<(i32, NewIngredient)> {
+        let name = optional(Some(self.name))?;
+        let food_group = optional(self.food_group)?;
+        let mut ingredient = NewIngredient {
+            name,
+            food_group,
+            description: optional(self.description),
+            name_scientific: optional(self.name_scientific),
+            food_subgroup: optional(self.food_subgroup),
+        };
+        Taxonomy::bundled().canonicalize(&mut ingredient);
+        Some((self.id, ingredient))
+    }
+}
+
+#[derive(Debug)]
+pub enum FoodbError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Csv(String),
+}
+
+impl fmt::Display for FoodbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FoodbError::Io(error) => write!(f, "could not read dump: {}", error),
+            FoodbError::Json(error) => write!(f, "invalid JSON dump: {}", error),
+            FoodbError::Csv(message) => write!(f, "invalid CSV dump: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for FoodbError {}
+
+impl From<io::Error> for FoodbError {
+    fn from(error: io::Error) -> Self {
+        FoodbError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for FoodbError {
+    fn from(error: serde_json::Error) -> Self {
+        FoodbError::Json(error)
+    }
+}
+
+/// Reads the foods of `Food.csv`, which needs at least the `id` and `name` columns.
+pub fn parse_csv_foods(text: &str) -> Result<Vec<Food>, FoodbError> {
+    let mut records = parse_csv(text).into_iter();
+    let headers: HashMap<String, usize> = match records.next() {
+        Some(headers) => headers
+            .into_iter()
+            .enumerate()
+            .map(|(index, header)| (header.trim().to_lowercase(), index))
+            .collect(),
+        None => return Ok(vec![]),
+    };
+    let column = |name: &str| {
+        headers
+            .get(name)
+            .copied()
+            .ok_or_else(|| FoodbError::Csv(format!("missing the {} column", name)))
+    };
+    let (id, name) = (column("id")?, column("name")?);
+    let other = |name: &str| headers.get(name).copied();
+    let (name_scientific, description, food_group, food_subgroup) = (
+        other("name_scientific"),
+        other("description"),
+        other("food_group"),
+        other("food_subgroup"),
+    );
+
+    records
+        .enumerate()
+        .map(|(index, record)| {
+            let field = |column: Option<usize>| column.and_then(|c| record.get(c)).cloned();
+            let raw_id = field(Some(id)).unwrap_or_default();
+            Ok(Food {
+                id: raw_id.trim().parse().map_err(|_| {
+                    FoodbError::Csv(format!("row {} has the invalid id {:?}", index + 1, raw_id))
+                })?,
+                name: field(Some(name)).unwrap_or_default(),
+                name_scientific: field(name_scientific),
+                description: field(description),
+                food_group: field(food_group),
+                food_subgroup: field(food_subgroup),
+            })
+        })
+        .collect()
+}
+
+/// Reads the foods of `Food.json`, either a JSON array or one object per
+/// line as FooDB ships it.
+pub fn parse_json_foods(text: &str) -> Result<Vec<Food>, FoodbError> {
+    if text.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(text)?);
+    }
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Reads the foods of the dump at `path`, as JSON when it ends in `.json`
+/// and as CSV otherwise.
+pub fn read_foods(path: impl AsRef<Path>) -> Result<Vec<Food>, FoodbError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("json") => parse_json_foods(&text),
+        _ => parse_csv_foods(&text),
+    }
+}
@@ -0,0 +1,8 @@
+//! Backend for Whats Cooking, serving the ingredients and recipes API the
+//! frontend expects on port 8082.
+pub mod foodb;
+pub mod routes;
+pub mod store;
+
+pub use routes::app;
+pub use store::Store;
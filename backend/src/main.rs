@@ -0,0 +1,26 @@
+use std::env;
+use std::net::SocketAddr;
+use whats_cooking_backend::store::DEFAULT_DATA_FILE;
+use whats_cooking_backend::{app, Store};
+
+const DEFAULT_PORT: u16 = 8082;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let port = match env::var("PORT") {
+        Ok(port) => port.parse()?,
+        Err(_) => DEFAULT_PORT,
+    };
+    let data_file = env::var("DATA_FILE").unwrap_or_else(|_| DEFAULT_DATA_FILE.to_string());
+
+    let store = Store::open(&data_file)?;
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    println!("serving {} on http://{}", data_file, addr);
+    axum::serve(listener, app(store))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,305 @@
+use crate::store::{Store, StoreError};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use tower_http::cors::CorsLayer;
+use whats_cooking_types::{
+    search::search, taxonomy::Taxonomy, BatchAction, BatchOutcome, BatchRequest, DeletedIngredient,
+    Envelope, ErrorBody, ImportOutcome, Ingredient, IngredientQuery, NewIngredient, NewRecipe,
+    Page, Recipe, SectionCount,
+};
+
+pub type AppState = Arc<Store>;
+
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound(String),
+    Invalid(String),
+    Store(StoreError),
+}
+
+impl From<StoreError> for ApiError {
+    fn from(error: StoreError) -> Self {
+        ApiError::Store(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::Invalid(message) => (StatusCode::UNPROCESSABLE_ENTITY, message),
+            ApiError::Store(error) => {
+                eprintln!("store error: {}", error);
+                (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+            }
+        };
+        (status, Json(ErrorBody { error: message })).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<Envelope<T>>, ApiError>;
+
+/// Builds the router for every endpoint the frontend talks to.
+pub fn app(store: Store) -> Router {
+    Router::new()
+        .route(
+            "/ingredients",
+            get(list_ingredients).post(create_ingredient),
+        )
+        .route("/ingredients/groups", get(list_food_groups))
+        .route("/ingredients/sections", get(list_sections))
+        .route("/ingredients/batch", post(batch_ingredients))
+        .route("/ingredients/deleted", get(list_deleted_ingredients))
+        .route("/ingredients/import", post(import_ingredients))
+        .route(
+            "/ingredients/{id}",
+            get(get_ingredient)
+                .put(update_ingredient)
+                .delete(delete_ingredient),
+        )
+        .route("/recipes", get(list_recipes).post(create_recipe))
+        .route("/recipes/{id}", get(get_recipe))
+        .layer(CorsLayer::permissive())
+        .with_state(Arc::new(store))
+}
+
+fn require(field: &str, value: &str) -> Result<(), ApiError> {
+    if value.trim().is_empty() {
+        return Err(ApiError::Invalid(format!("{} is required", field)));
+    }
+    Ok(())
+}
+
+/// Ingredients in the group and matching the search of `query`, the best
+/// matches first.
+fn matching_ingredients(store: &Store, query: &IngredientQuery) -> Vec<Ingredient> {
+    let mut ingredients = store.list_ingredients();
+    if let Some(group) = query.group.as_deref().filter(|g| !g.trim().is_empty()) {
+        ingredients.retain(|ingredient| {
+            ingredient
+                .food_group
+                .as_deref()
+                .is_some_and(|g| g.eq_ignore_ascii_case(group.trim()))
+        });
+    }
+    if let Some(q) = &query.q {
+        ingredients = search(&ingredients, q)
+            .into_iter()
+            .map(|hit| hit.ingredient.clone())
+            .collect();
+    }
+    ingredients
+}
+
+async fn list_ingredients(
+    State(store): State<AppState>,
+    Query(query): Query<IngredientQuery>,
+) -> ApiResult<Page<Ingredient>> {
+    let mut ingredients = matching_ingredients(&store, &query);
+    if let Some(sort) = query.sort {
+        // stable, so ties keep their relevance order
+        ingredients.sort_by(|a, b| sort.compare(a, b));
+    }
+    let total = ingredients.len();
+    let offset = query.offset.unwrap_or(0).min(total);
+    let limit = query.limit.unwrap_or(total);
+    let items = ingredients.into_iter().skip(offset).take(limit).collect();
+    Ok(Json(Envelope::new(Page {
+        items,
+        total,
+        offset,
+    })))
+}
+
+/// Every food group in use, sorted, for filtering the ingredients by group.
+async fn list_food_groups(State(store): State<AppState>) -> ApiResult<Vec<String>> {
+    let groups: BTreeSet<String> = store
+        .list_ingredients()
+        .into_iter()
+        .filter_map(|ingredient| ingredient.food_group)
+        .filter(|group| !group.trim().is_empty())
+        .collect();
+    Ok(Json(Envelope::new(groups.into_iter().collect())))
+}
+
+/// How many ingredients matching the query are in each food group and
+/// subgroup, so grouped lists can count the rows they haven't fetched.
+async fn list_sections(
+    State(store): State<AppState>,
+    Query(query): Query<IngredientQuery>,
+) -> ApiResult<Vec<SectionCount>> {
+    let mut counts: BTreeMap<(Option<String>, Option<String>), usize> = BTreeMap::new();
+    for ingredient in matching_ingredients(&store, &query) {
+        *counts
+            .entry((ingredient.food_group, ingredient.food_subgroup))
+            .or_default() += 1;
+    }
+    let sections = counts
+        .into_iter()
+        .map(|((food_group, food_subgroup), count)| SectionCount {
+            food_group,
+            food_subgroup,
+            count,
+        })
+        .collect();
+    Ok(Json(Envelope::new(sections)))
+}
+
+async fn create_ingredient(
+    State(store): State<AppState>,
+    Json(mut new): Json<NewIngredient>,
+) -> ApiResult<String> {
+    require("name", &new.name)?;
+    require("food_group", &new.food_group)?;
+    Taxonomy::bundled().canonicalize(&mut new);
+    let ingredient = store.create_ingredient(new)?;
+    Ok(Json(Envelope::new(format!(
+        "Created ingredient {}",
+        ingredient.id
+    ))))
+}
+
+/// Creates many ingredients at once, e.g. from a CSV file. Invalid rows are
+/// reported and skipped instead of failing the whole import.
+async fn import_ingredients(
+    State(store): State<AppState>,
+    Json(rows): Json<Vec<NewIngredient>>,
+) -> ApiResult<Vec<ImportOutcome>> {
+    let mut valid = vec![];
+    let mut errors = vec![];
+    for mut row in rows {
+        let missing = [("name", &row.name), ("food_group", &row.food_group)]
+            .iter()
+            .find(|(_, value)| value.trim().is_empty())
+            .map(|(field, _)| format!("{} is required", field));
+        if missing.is_none() {
+            Taxonomy::bundled().canonicalize(&mut row);
+            valid.push(row);
+        }
+        errors.push(missing);
+    }
+    let mut created = store.create_ingredients(valid)?.into_iter();
+    let outcomes = errors
+        .into_iter()
+        .map(|error| match error {
+            Some(error) => ImportOutcome {
+                id: None,
+                error: Some(error),
+            },
+            None => ImportOutcome {
+                id: created.next().map(|ingredient| ingredient.id),
+                error: None,
+            },
+        })
+        .collect();
+    Ok(Json(Envelope::new(outcomes)))
+}
+
+async fn get_ingredient(
+    State(store): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Ingredient> {
+    match store.get_ingredient(id) {
+        Some(ingredient) => Ok(Json(Envelope::new(ingredient))),
+        None => Err(ApiError::NotFound(format!("No ingredient with id {}", id))),
+    }
+}
+
+async fn update_ingredient(
+    State(store): State<AppState>,
+    Path(id): Path<i32>,
+    Json(mut update): Json<NewIngredient>,
+) -> ApiResult<Ingredient> {
+    require("name", &update.name)?;
+    require("food_group", &update.food_group)?;
+    Taxonomy::bundled().canonicalize(&mut update);
+    match store.update_ingredient(id, update)? {
+        Some(ingredient) => Ok(Json(Envelope::new(ingredient))),
+        None => Err(ApiError::NotFound(format!("No ingredient with id {}", id))),
+    }
+}
+
+async fn delete_ingredient(
+    State(store): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<String> {
+    match store.delete_ingredient(id)? {
+        Some(_) => Ok(Json(Envelope::new(format!("Deleted ingredient {}", id)))),
+        None => Err(ApiError::NotFound(format!("No ingredient with id {}", id))),
+    }
+}
+
+async fn list_deleted_ingredients(
+    State(store): State<AppState>,
+) -> ApiResult<Vec<DeletedIngredient>> {
+    Ok(Json(Envelope::new(store.list_deleted_ingredients())))
+}
+
+async fn batch_ingredients(
+    State(store): State<AppState>,
+    Json(batch): Json<BatchRequest>,
+) -> ApiResult<Vec<BatchOutcome>> {
+    let done = match &batch.action {
+        BatchAction::Delete => store.delete_ingredients(&batch.ids)?,
+        BatchAction::Restore => store.restore_ingredients(&batch.ids)?,
+        BatchAction::SetFoodGroup {
+            food_group,
+            food_subgroup,
+        } => {
+            require("food_group", food_group)?;
+            let (group, subgroup) =
+                Taxonomy::bundled().canonical_names(food_group, food_subgroup.as_deref());
+            store.set_food_group(&batch.ids, &group, subgroup.as_deref())?
+        }
+    };
+    let outcomes = batch
+        .ids
+        .iter()
+        .map(|&id| BatchOutcome {
+            id,
+            error: match (done.contains(&id), &batch.action) {
+                (true, _) => None,
+                (false, BatchAction::Restore) => {
+                    Some(format!("No deleted ingredient with id {}", id))
+                }
+                (false, _) => Some(format!("No ingredient with id {}", id)),
+            },
+        })
+        .collect();
+    Ok(Json(Envelope::new(outcomes)))
+}
+
+async fn list_recipes(State(store): State<AppState>) -> ApiResult<Vec<Recipe>> {
+    Ok(Json(Envelope::new(store.list_recipes())))
+}
+
+async fn get_recipe(State(store): State<AppState>, Path(id): Path<i32>) -> ApiResult<Recipe> {
+    match store.get_recipe(id) {
+        Some(recipe) => Ok(Json(Envelope::new(recipe))),
+        None => Err(ApiError::NotFound(format!("No recipe with id {}", id))),
+    }
+}
+
+async fn create_recipe(
+    State(store): State<AppState>,
+    Json(new): Json<NewRecipe>,
+) -> ApiResult<String> {
+    require("name", &new.name)?;
+    for ingredient in &new.ingredients {
+        require("ingredient name", &ingredient.name)?;
+        if let Some(id) = ingredient.ingredient_id {
+            if store.get_ingredient(id).is_none() {
+                return Err(ApiError::Invalid(format!("No ingredient with id {}", id)));
+            }
+        }
+    }
+    let recipe = store.create_recipe(new)?;
+    Ok(Json(Envelope::new(format!("Created recipe {}", recipe.id))))
+}
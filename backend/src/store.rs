@@ -0,0 +1,388 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use whats_cooking_types::{DeletedIngredient, Ingredient, NewIngredient, NewRecipe, Recipe};
+
+/// Data file used when `DATA_FILE` is not set.
+pub const DEFAULT_DATA_FILE: &str = "whats-cooking.json";
+
+/// How long deleted ingredients can be restored for.
+pub const DELETED_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(error) => write!(f, "could not access data file: {}", error),
+            StoreError::Json(error) => write!(f, "could not read data file: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(error: io::Error) -> Self {
+        StoreError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(error: serde_json::Error) -> Self {
+        StoreError::Json(error)
+    }
+}
+
+/// What [`Store::import_foodb`] did with the foods it was given.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoodbImport {
+    /// Foods added as new ingredients.
+    pub created: usize,
+    /// Foods whose name matched an ingredient that now refers to them.
+    pub linked: usize,
+    /// Foods whose name matched an ingredient already referring to a food.
+    pub duplicates: usize,
+}
+
+/// Everything the backend knows about, written to disk as a single JSON document.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+struct Data {
+    next_ingredient_id: i32,
+    ingredients: Vec<Ingredient>,
+    /// Deleted ingredients that can still be restored, oldest first.
+    #[serde(default)]
+    deleted_ingredients: Vec<DeletedIngredient>,
+    next_recipe_id: i32,
+    recipes: Vec<Recipe>,
+}
+
+impl Data {
+    /// Moves `ingredients` to the recently deleted list, forgetting the ones
+    /// deleted longer than [`DELETED_RETENTION`] ago.
+    fn trash(&mut self, ingredients: Vec<Ingredient>) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let oldest = now.saturating_sub(DELETED_RETENTION.as_secs());
+        self.deleted_ingredients
+            .retain(|deleted| deleted.deleted_at >= oldest);
+        self.deleted_ingredients
+            .extend(ingredients.into_iter().map(|ingredient| DeletedIngredient {
+                ingredient,
+                deleted_at: now,
+            }));
+    }
+}
+
+/// File backed storage for ingredients and recipes.
+///
+/// The whole data set is kept in memory and rewritten to `path` after every
+/// change. Changes are made to a copy that only replaces the data in memory
+/// once it is written, so a failed write changes nothing. A store without a
+/// path (see [`Store::in_memory`]) never touches disk.
+pub struct Store {
+    path: Option<PathBuf>,
+    data: Mutex<Data>,
+}
+
+impl Store {
+    /// Opens the data file at `path`, starting empty if it does not exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref().to_path_buf();
+        let data = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Data::default(),
+            Err(error) => return Err(error.into()),
+        };
+        Ok(Self {
+            path: Some(path),
+            data: Mutex::new(data),
+        })
+    }
+
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            data: Mutex::new(Data::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Data> {
+        // Changes are made to a copy, so a panic while holding the lock leaves
+        // the data as it was before the failed change and it is still safe to use.
+        self.data
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn save(&self, data: &Data) -> Result<(), StoreError> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        // Write next to the real file first so a crash never leaves it half written.
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(data)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Writes `changed` and only then makes it the data in memory.
+    fn commit(&self, data: &mut Data, changed: Data) -> Result<(), StoreError> {
+        self.save(&changed)?;
+        *data = changed;
+        Ok(())
+    }
+
+    pub fn list_ingredients(&self) -> Vec<Ingredient> {
+        self.lock().ingredients.clone()
+    }
+
+    pub fn get_ingredient(&self, id: i32) -> Option<Ingredient> {
+        self.lock().ingredients.iter().find(|i| i.id == id).cloned()
+    }
+
+    pub fn create_ingredient(&self, new: NewIngredient) -> Result<Ingredient, StoreError> {
+        let mut created = self.create_ingredients(vec![new])?;
+        Ok(created.remove(0))
+    }
+
+    /// Adds every ingredient of `new` with a single write, in order.
+    pub fn create_ingredients(
+        &self,
+        new: Vec<NewIngredient>,
+    ) -> Result<Vec<Ingredient>, StoreError> {
+        let mut data = self.lock();
+        let mut changed = data.clone();
+        let mut created = vec![];
+        for new in new {
+            changed.next_ingredient_id += 1;
+            let ingredient = Ingredient {
+                id: changed.next_ingredient_id,
+                name: new.name,
+                name_scientific: new.name_scientific,
+                description: new.description,
+                food_group: Some(new.food_group),
+                food_subgroup: new.food_subgroup,
+                foodb_id: None,
+            };
+            changed.ingredients.push(ingredient.clone());
+            created.push(ingredient);
+        }
+        if !created.is_empty() {
+            self.commit(&mut data, changed)?;
+        }
+        Ok(created)
+    }
+
+    /// Adds FooDB foods, given as their FooDB id and ingredient, with a single
+    /// write.
+    ///
+    /// Names are compared ignoring case and surrounding spaces. A food named
+    /// like an ingredient that has no FooDB id yet is linked to it instead of
+    /// being added, and later foods with an already used name are skipped.
+    pub fn import_foodb(
+        &self,
+        foods: Vec<(i32, NewIngredient)>,
+    ) -> Result<FoodbImport, StoreError> {
+        let key = |name: &str| name.trim().to_lowercase();
+        let mut data = self.lock();
+        let mut changed = data.clone();
+        let mut names: HashMap<String, usize> = changed
+            .ingredients
+            .iter()
+            .enumerate()
+            .map(|(index, ingredient)| (key(&ingredient.name), index))
+            .collect();
+        let mut summary = FoodbImport::default();
+        for (foodb_id, new) in foods {
+            if let Some(&index) = names.get(&key(&new.name)) {
+                let existing = &mut changed.ingredients[index];
+                if existing.foodb_id.is_none() {
+                    existing.foodb_id = Some(foodb_id);
+                    summary.linked += 1;
+                } else {
+                    summary.duplicates += 1;
+                }
+                continue;
+            }
+            changed.next_ingredient_id += 1;
+            names.insert(key(&new.name), changed.ingredients.len());
+            let ingredient = Ingredient {
+                id: changed.next_ingredient_id,
+                name: new.name,
+                name_scientific: new.name_scientific,
+                description: new.description,
+                food_group: Some(new.food_group),
+                food_subgroup: new.food_subgroup,
+                foodb_id: Some(foodb_id),
+            };
+            changed.ingredients.push(ingredient);
+            summary.created += 1;
+        }
+        if summary.created + summary.linked > 0 {
+            self.commit(&mut data, changed)?;
+        }
+        Ok(summary)
+    }
+
+    /// Replaces every field of the ingredient with `id`, returning the updated
+    /// ingredient if it existed.
+    pub fn update_ingredient(
+        &self,
+        id: i32,
+        update: NewIngredient,
+    ) -> Result<Option<Ingredient>, StoreError> {
+        let mut data = self.lock();
+        let position = match data.ingredients.iter().position(|i| i.id == id) {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+        let mut changed = data.clone();
+        let ingredient = &mut changed.ingredients[position];
+        *ingredient = Ingredient {
+            id,
+            name: update.name,
+            name_scientific: update.name_scientific,
+            description: update.description,
+            food_group: Some(update.food_group),
+            food_subgroup: update.food_subgroup,
+            // not editable, it only comes from the FooDB importer
+            foodb_id: ingredient.foodb_id,
+        };
+        let ingredient = ingredient.clone();
+        self.commit(&mut data, changed)?;
+        Ok(Some(ingredient))
+    }
+
+    /// Removes the ingredient with `id`, returning it if it existed.
+    pub fn delete_ingredient(&self, id: i32) -> Result<Option<Ingredient>, StoreError> {
+        let mut data = self.lock();
+        let position = match data.ingredients.iter().position(|i| i.id == id) {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+        let mut changed = data.clone();
+        let ingredient = changed.ingredients.remove(position);
+        changed.trash(vec![ingredient.clone()]);
+        self.commit(&mut data, changed)?;
+        Ok(Some(ingredient))
+    }
+
+    /// Removes the ingredients with `ids`, returning the ids that existed.
+    pub fn delete_ingredients(&self, ids: &[i32]) -> Result<Vec<i32>, StoreError> {
+        let mut data = self.lock();
+        let mut changed = data.clone();
+        let (deleted, kept) = changed
+            .ingredients
+            .drain(..)
+            .partition::<Vec<_>, _>(|i| ids.contains(&i.id));
+        changed.ingredients = kept;
+        let deleted_ids = deleted.iter().map(|i| i.id).collect::<Vec<_>>();
+        if !deleted.is_empty() {
+            changed.trash(deleted);
+            self.commit(&mut data, changed)?;
+        }
+        Ok(deleted_ids)
+    }
+
+    /// Recently deleted ingredients, most recently deleted first.
+    pub fn list_deleted_ingredients(&self) -> Vec<DeletedIngredient> {
+        let mut deleted = self.lock().deleted_ingredients.clone();
+        deleted.reverse();
+        deleted
+    }
+
+    /// Brings back the recently deleted ingredients with `ids`, returning the
+    /// ids that were found.
+    pub fn restore_ingredients(&self, ids: &[i32]) -> Result<Vec<i32>, StoreError> {
+        let mut data = self.lock();
+        let mut changed = data.clone();
+        let (restored, kept) = changed
+            .deleted_ingredients
+            .drain(..)
+            .partition::<Vec<_>, _>(|d| ids.contains(&d.ingredient.id));
+        changed.deleted_ingredients = kept;
+        let mut restored_ids = vec![];
+        for DeletedIngredient { ingredient, .. } in restored {
+            restored_ids.push(ingredient.id);
+            // ids only grow, so this puts it back where it was
+            let position = changed
+                .ingredients
+                .partition_point(|i| i.id < ingredient.id);
+            changed.ingredients.insert(position, ingredient);
+        }
+        if !restored_ids.is_empty() {
+            self.commit(&mut data, changed)?;
+        }
+        Ok(restored_ids)
+    }
+
+    /// Moves the ingredients with `ids` to another group, and subgroup if
+    /// given, returning the ids that existed.
+    pub fn set_food_group(
+        &self,
+        ids: &[i32],
+        food_group: &str,
+        food_subgroup: Option<&str>,
+    ) -> Result<Vec<i32>, StoreError> {
+        let mut data = self.lock();
+        let mut changed = data.clone();
+        let mut updated = vec![];
+        for ingredient in changed
+            .ingredients
+            .iter_mut()
+            .filter(|i| ids.contains(&i.id))
+        {
+            ingredient.food_group = Some(food_group.to_string());
+            if let Some(food_subgroup) = food_subgroup {
+                ingredient.food_subgroup = Some(food_subgroup.to_string());
+            }
+            updated.push(ingredient.id);
+        }
+        if !updated.is_empty() {
+            self.commit(&mut data, changed)?;
+        }
+        Ok(updated)
+    }
+
+    pub fn list_recipes(&self) -> Vec<Recipe> {
+        self.lock().recipes.clone()
+    }
+
+    pub fn get_recipe(&self, id: i32) -> Option<Recipe> {
+        self.lock().recipes.iter().find(|r| r.id == id).cloned()
+    }
+
+    pub fn create_recipe(&self, new: NewRecipe) -> Result<Recipe, StoreError> {
+        let mut data = self.lock();
+        let mut changed = data.clone();
+        changed.next_recipe_id += 1;
+        let recipe = Recipe {
+            id: changed.next_recipe_id,
+            name: new.name,
+            description: new.description,
+            link: new.link,
+            url: new.url,
+            ingredients: new.ingredients,
+            steps: new.steps,
+            yields: new.yields,
+            prep_time: new.prep_time,
+            cook_time: new.cook_time,
+        };
+        changed.recipes.push(recipe.clone());
+        self.commit(&mut data, changed)?;
+        Ok(recipe)
+    }
+}
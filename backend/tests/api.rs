@@ -0,0 +1,521 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use whats_cooking_backend::{app, Store};
+use whats_cooking_types::NewIngredient;
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    let request = match body {
+        Some(body) => request.body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn creates_and_lists_ingredients() {
+    let app = app(Store::in_memory());
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/ingredients",
+        Some(json!({ "name": "Basil", "food_group": "Herbs and Spices", "decription": "Sweet" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "GET", "/ingredients", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"]["items"][0]["name"], "Basil");
+    assert_eq!(body["response"]["items"][0]["decription"], "Sweet");
+    assert_eq!(body["response"]["items"][0]["id"], 1);
+
+    let (status, body) = send(&app, "GET", "/ingredients/1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"]["name"], "Basil");
+    let (status, _) = send(&app, "GET", "/ingredients/2", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn rejects_ingredients_without_a_food_group() {
+    let app = app(Store::in_memory());
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/ingredients",
+        Some(json!({ "name": "Basil", "food_group": "" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "food_group is required");
+}
+
+#[tokio::test]
+async fn deletes_ingredients() {
+    let app = app(Store::in_memory());
+    send(
+        &app,
+        "POST",
+        "/ingredients",
+        Some(json!({ "name": "Basil", "food_group": "Herbs and Spices" })),
+    )
+    .await;
+
+    let (status, _) = send(&app, "DELETE", "/ingredients/1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "DELETE", "/ingredients/1", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, body) = send(&app, "GET", "/ingredients", None).await;
+    assert_eq!(body["response"]["items"], json!([]));
+}
+
+#[tokio::test]
+async fn searches_and_pages_ingredients() {
+    let app = app(Store::in_memory());
+    for (name, group) in [
+        ("Basil", "Herbs and Spices"),
+        ("Thai basil", "Herbs and Spices"),
+        ("Tomato", "Vegetables"),
+        ("Potato", "Vegetables"),
+        ("Sweet potato", "Vegetables"),
+    ] {
+        send(
+            &app,
+            "POST",
+            "/ingredients",
+            Some(json!({ "name": name, "food_group": group })),
+        )
+        .await;
+    }
+
+    let (status, body) = send(&app, "GET", "/ingredients?q=potatoe", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"]["total"], 2);
+    assert_eq!(body["response"]["items"][0]["name"], "Potato");
+
+    let (_, body) = send(&app, "GET", "/ingredients?group=vegetables&limit=2", None).await;
+    assert_eq!(body["response"]["total"], 3);
+    assert_eq!(body["response"]["offset"], 0);
+    assert_eq!(body["response"]["items"].as_array().unwrap().len(), 2);
+
+    let (_, body) = send(
+        &app,
+        "GET",
+        "/ingredients?group=Vegetables&limit=2&offset=2",
+        None,
+    )
+    .await;
+    assert_eq!(body["response"]["offset"], 2);
+    assert_eq!(body["response"]["items"][0]["name"], "Sweet potato");
+
+    let (_, body) = send(&app, "GET", "/ingredients/groups", None).await;
+    assert_eq!(body["response"], json!(["Herbs and Spices", "Vegetables"]));
+}
+
+#[tokio::test]
+async fn counts_matching_ingredients_by_section() {
+    let app = app(Store::in_memory());
+    for (name, group, subgroup) in [
+        ("Basil", "Herbs and Spices", "Herbs"),
+        ("Potato", "Vegetables", "Root vegetables"),
+        ("Sweet potato", "Vegetables", "Root vegetables"),
+        ("Potato chips", "Snack foods", "Chips"),
+    ] {
+        send(
+            &app,
+            "POST",
+            "/ingredients",
+            Some(json!({ "name": name, "food_group": group, "food_subgroup": subgroup })),
+        )
+        .await;
+    }
+
+    let (status, body) = send(&app, "GET", "/ingredients/sections?q=potato", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["response"],
+        json!([
+            { "food_group": "Snack foods", "food_subgroup": "Chips", "count": 1 },
+            { "food_group": "Vegetables", "food_subgroup": "Root vegetables", "count": 2 },
+        ])
+    );
+
+    let (_, body) = send(
+        &app,
+        "GET",
+        "/ingredients/sections?group=herbs%20and%20spices",
+        None,
+    )
+    .await;
+    assert_eq!(
+        body["response"],
+        json!([{ "food_group": "Herbs and Spices", "food_subgroup": "Herbs", "count": 1 }])
+    );
+}
+
+#[tokio::test]
+async fn sorts_ingredients() {
+    let app = app(Store::in_memory());
+    for (name, group) in [
+        ("tomato", "Vegetables"),
+        ("Basil", "Herbs and Spices"),
+        ("Apple", "Fruits"),
+    ] {
+        send(
+            &app,
+            "POST",
+            "/ingredients",
+            Some(json!({ "name": name, "food_group": group })),
+        )
+        .await;
+    }
+    let names = |body: &Value| {
+        body["response"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let (_, body) = send(&app, "GET", "/ingredients?sort=name", None).await;
+    assert_eq!(names(&body), ["Apple", "Basil", "tomato"]);
+    let (_, body) = send(&app, "GET", "/ingredients?sort=-food_group", None).await;
+    assert_eq!(names(&body), ["tomato", "Basil", "Apple"]);
+    let (_, body) = send(&app, "GET", "/ingredients?sort=-id&limit=1", None).await;
+    assert_eq!(names(&body), ["Apple"]);
+
+    let (status, _) = send(&app, "GET", "/ingredients?sort=color", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn updates_ingredients() {
+    let app = app(Store::in_memory());
+    send(
+        &app,
+        "POST",
+        "/ingredients",
+        Some(json!({ "name": "Basil", "food_group": "Vegetables" })),
+    )
+    .await;
+
+    let (status, body) = send(
+        &app,
+        "PUT",
+        "/ingredients/1",
+        Some(
+            json!({ "name": "Basil", "food_group": "Herbs and Spices", "food_subgroup": "Herbs" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"]["food_group"], "Herbs and Spices");
+    assert_eq!(body["response"]["food_subgroup"], "Herbs");
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        "/ingredients/2",
+        Some(json!({ "name": "Mint", "food_group": "Herbs and Spices" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn spells_known_food_groups_like_the_taxonomy() {
+    let app = app(Store::in_memory());
+    send(
+        &app,
+        "POST",
+        "/ingredients",
+        Some(
+            json!({ "name": "Basil", "food_group": "herbs and spices", "food_subgroup": "herbs" }),
+        ),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/ingredients",
+        Some(json!({ "name": "Cricket", "food_group": "Insects " })),
+    )
+    .await;
+
+    let (_, body) = send(&app, "GET", "/ingredients", None).await;
+    assert_eq!(
+        body["response"]["items"][0]["food_group"],
+        "Herbs and Spices"
+    );
+    assert_eq!(body["response"]["items"][0]["food_subgroup"], "Herbs");
+    assert_eq!(body["response"]["items"][1]["food_group"], "Insects");
+}
+
+#[tokio::test]
+async fn applies_batch_actions_to_ingredients() {
+    let app = app(Store::in_memory());
+    for name in ["Basil", "Mint", "Potato"] {
+        send(
+            &app,
+            "POST",
+            "/ingredients",
+            Some(json!({ "name": name, "food_group": "Vegetables" })),
+        )
+        .await;
+    }
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/ingredients/batch",
+        Some(json!({
+            "ids": [1, 2, 9],
+            "action": { "type": "set_food_group", "food_group": "herbs and spices", "food_subgroup": "Herbs" },
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["response"],
+        json!([
+            { "id": 1, "error": null },
+            { "id": 2, "error": null },
+            { "id": 9, "error": "No ingredient with id 9" },
+        ])
+    );
+    let (_, body) = send(&app, "GET", "/ingredients/2", None).await;
+    assert_eq!(body["response"]["food_group"], "Herbs and Spices");
+    assert_eq!(body["response"]["food_subgroup"], "Herbs");
+
+    // without a subgroup only the group changes
+    send(
+        &app,
+        "POST",
+        "/ingredients/batch",
+        Some(json!({ "ids": [2], "action": { "type": "set_food_group", "food_group": "Herbs and Spices" } })),
+    )
+    .await;
+    let (_, body) = send(&app, "GET", "/ingredients/2", None).await;
+    assert_eq!(body["response"]["food_subgroup"], "Herbs");
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/ingredients/batch",
+        Some(json!({ "ids": [1], "action": { "type": "set_food_group", "food_group": " " } })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (_, body) = send(
+        &app,
+        "POST",
+        "/ingredients/batch",
+        Some(json!({ "ids": [1, 3], "action": { "type": "delete" } })),
+    )
+    .await;
+    assert_eq!(body["response"][1], json!({ "id": 3, "error": null }));
+    let (_, body) = send(&app, "GET", "/ingredients", None).await;
+    assert_eq!(body["response"]["total"], 1);
+    assert_eq!(body["response"]["items"][0]["name"], "Mint");
+}
+
+#[tokio::test]
+async fn restores_deleted_ingredients() {
+    let app = app(Store::in_memory());
+    for name in ["Basil", "Mint", "Potato"] {
+        send(
+            &app,
+            "POST",
+            "/ingredients",
+            Some(json!({ "name": name, "food_group": "Vegetables" })),
+        )
+        .await;
+    }
+    send(&app, "DELETE", "/ingredients/1", None).await;
+    send(
+        &app,
+        "POST",
+        "/ingredients/batch",
+        Some(json!({ "ids": [3], "action": { "type": "delete" } })),
+    )
+    .await;
+
+    let (status, body) = send(&app, "GET", "/ingredients/deleted", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"][0]["ingredient"]["name"], "Potato");
+    assert_eq!(body["response"][1]["ingredient"]["name"], "Basil");
+    assert!(body["response"][0]["deleted_at"].as_u64().unwrap() > 0);
+
+    let (_, body) = send(
+        &app,
+        "POST",
+        "/ingredients/batch",
+        Some(json!({ "ids": [1, 2], "action": { "type": "restore" } })),
+    )
+    .await;
+    assert_eq!(
+        body["response"],
+        json!([
+            { "id": 1, "error": null },
+            { "id": 2, "error": "No deleted ingredient with id 2" },
+        ])
+    );
+    let (_, body) = send(&app, "GET", "/ingredients", None).await;
+    assert_eq!(body["response"]["items"][0]["name"], "Basil");
+    assert_eq!(body["response"]["items"][1]["name"], "Mint");
+    let (_, body) = send(&app, "GET", "/ingredients/deleted", None).await;
+    assert_eq!(body["response"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn creates_and_lists_recipes() {
+    let app = app(Store::in_memory());
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/recipes",
+        Some(json!({ "name": "Pesto", "description": "Green", "link": null, "url": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"], "Created recipe 1");
+
+    let (status, body) = send(&app, "GET", "/recipes", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"][0]["name"], "Pesto");
+    assert_eq!(body["response"][0]["id"], 1);
+}
+
+#[test]
+fn store_persists_between_opens() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.json");
+
+    let store = Store::open(&path).unwrap();
+    store
+        .create_ingredient(NewIngredient {
+            name: String::from("Basil"),
+            food_group: String::from("Herbs and Spices"),
+            ..NewIngredient::default()
+        })
+        .unwrap();
+    drop(store);
+
+    let store = Store::open(&path).unwrap();
+    let ingredients = store.list_ingredients();
+    assert_eq!(ingredients.len(), 1);
+    assert_eq!(ingredients[0].name, "Basil");
+}
+
+#[test]
+fn store_keeps_memory_unchanged_when_saving_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.json");
+    let store = Store::open(&path).unwrap();
+    // a directory in place of the data file makes every save fail
+    std::fs::create_dir(&path).unwrap();
+
+    let result = store.create_ingredient(NewIngredient {
+        name: String::from("Basil"),
+        food_group: String::from("Herbs and Spices"),
+        ..NewIngredient::default()
+    });
+    assert!(result.is_err());
+    assert!(store.list_ingredients().is_empty());
+}
+
+#[tokio::test]
+async fn recipes_link_to_existing_ingredients() {
+    let app = app(Store::in_memory());
+    send(
+        &app,
+        "POST",
+        "/ingredients",
+        Some(json!({ "name": "Basil", "food_group": "Herbs and Spices" })),
+    )
+    .await;
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/recipes",
+        Some(json!({
+            "name": "Pesto",
+            "ingredients": [{ "ingredient_id": 1, "name": "Basil" }, { "ingredient_id": null, "name": "Pine nuts" }],
+            "steps": ["Blend everything"],
+            "yields": "1 jar",
+            "prep_time": 10,
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "GET", "/recipes/1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["response"]["ingredients"][0]["ingredient_id"], 1);
+    assert_eq!(body["response"]["steps"][0], "Blend everything");
+    assert_eq!(body["response"]["prep_time"], 10);
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/recipes",
+        Some(json!({ "name": "Pesto", "ingredients": [{ "ingredient_id": 7, "name": "Garlic" }] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "No ingredient with id 7");
+}
+
+#[tokio::test]
+async fn imports_valid_rows_and_reports_the_rest() {
+    let app = app(Store::in_memory());
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/ingredients/import",
+        Some(json!([
+            { "name": "Basil", "food_group": "herbs and spices" },
+            { "name": "", "food_group": "Fruits" },
+            { "name": "Apple", "food_group": " " },
+            { "name": "Pear", "food_group": "Fruits" },
+        ])),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["response"],
+        json!([
+            { "id": 1, "error": null },
+            { "id": null, "error": "name is required" },
+            { "id": null, "error": "food_group is required" },
+            { "id": 2, "error": null },
+        ])
+    );
+
+    let (_, body) = send(&app, "GET", "/ingredients", None).await;
+    assert_eq!(body["response"]["total"], 2);
+    assert_eq!(
+        body["response"]["items"][0]["food_group"],
+        "Herbs and Spices"
+    );
+}
@@ -0,0 +1,123 @@
+use whats_cooking_backend::foodb::{parse_csv_foods, parse_json_foods, Food};
+use whats_cooking_backend::store::FoodbImport;
+use whats_cooking_backend::Store;
+use whats_cooking_types::NewIngredient;
+
+const FOOD_CSV: &str = "\
+id,name,name_scientific,description,itis_id,food_group,food_subgroup,public_id
+1,Angelica,Angelica keiskei,\"Angelica is a genus of about 60 species, ...\",,Herbs and Spices,Herbs,FOOD00001
+2,Savoy cabbage,Brassica oleracea var. sabauda,,,vegetables,Cabbages,FOOD00002
+3,Mystery,,,,,,FOOD00003
+";
+
+fn food(id: i32, name: &str, group: &str) -> (i32, NewIngredient) {
+    (
+        id,
+        NewIngredient {
+            name: String::from(name),
+            food_group: String::from(group),
+            ..NewIngredient::default()
+        },
+    )
+}
+
+#[test]
+fn reads_csv_dumps() {
+    let foods = parse_csv_foods(FOOD_CSV).unwrap();
+    assert_eq!(foods.len(), 3);
+    assert_eq!(foods[0].id, 1);
+    assert_eq!(
+        foods[0].name_scientific.as_deref(),
+        Some("Angelica keiskei")
+    );
+    assert_eq!(
+        foods[0].description.as_deref(),
+        Some("Angelica is a genus of about 60 species, ...")
+    );
+
+    let (id, cabbage) = foods[1].clone().into_ingredient().unwrap();
+    assert_eq!(id, 2);
+    assert_eq!(cabbage.food_group, "Vegetables");
+    assert_eq!(cabbage.description, None);
+    // every ingredient needs a food group
+    assert_eq!(foods[2].clone().into_ingredient(), None);
+
+    assert!(parse_csv_foods("name\nBasil").is_err());
+    assert!(parse_csv_foods("id,name\nFOOD1,Basil").is_err());
+}
+
+#[test]
+fn reads_json_arrays_and_lines() {
+    let lines = r#"{"id":1,"name":"Angelica","food_group":"Herbs and Spices","itis_id":null}
+{"id":2,"name":"Savoy cabbage","food_group":"Vegetables","food_subgroup":null}
+"#;
+    let array = r#"[{"id":1,"name":"Angelica","food_group":"Herbs and Spices"},
+        {"id":2,"name":"Savoy cabbage","food_group":"Vegetables"}]"#;
+    let foods = parse_json_foods(lines).unwrap();
+    assert_eq!(foods, parse_json_foods(array).unwrap());
+    assert_eq!(
+        foods[1],
+        Food {
+            id: 2,
+            name: String::from("Savoy cabbage"),
+            name_scientific: None,
+            description: None,
+            food_group: Some(String::from("Vegetables")),
+            food_subgroup: None,
+        }
+    );
+}
+
+#[test]
+fn imports_foods_once_by_name() {
+    let store = Store::in_memory();
+    let basil = store
+        .create_ingredient(NewIngredient {
+            name: String::from("Basil"),
+            food_group: String::from("Herbs and Spices"),
+            ..NewIngredient::default()
+        })
+        .unwrap();
+
+    let summary = store
+        .import_foodb(vec![
+            food(10, " basil ", "Herbs and Spices"),
+            food(11, "Angelica", "Herbs and Spices"),
+            food(12, "ANGELICA", "Herbs and Spices"),
+        ])
+        .unwrap();
+    assert_eq!(
+        summary,
+        FoodbImport {
+            created: 1,
+            linked: 1,
+            duplicates: 1,
+        }
+    );
+    let ingredients = store.list_ingredients();
+    assert_eq!(ingredients.len(), 2);
+    assert_eq!(ingredients[0].foodb_id, Some(10));
+    assert_eq!(ingredients[1].name, "Angelica");
+    assert_eq!(ingredients[1].foodb_id, Some(11));
+
+    // importing the same dump again changes nothing
+    let again = store
+        .import_foodb(vec![food(11, "Angelica", "Herbs and Spices")])
+        .unwrap();
+    assert_eq!(again.duplicates, 1);
+    assert_eq!(store.list_ingredients().len(), 2);
+
+    // editing an ingredient keeps its reference
+    let updated = store
+        .update_ingredient(
+            basil.id,
+            NewIngredient {
+                name: String::from("Sweet basil"),
+                food_group: String::from("Herbs and Spices"),
+                ..NewIngredient::default()
+            },
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.foodb_id, Some(10));
+}
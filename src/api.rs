@@ -0,0 +1,204 @@
+use crate::config;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use whats_cooking_types::Envelope;
+use yew::{
+    format::{Json, Nothing, Text},
+    services::{
+        fetch::{FetchTask, Request, Response},
+        FetchService,
+    },
+    Callback,
+};
+
+pub use whats_cooking_types::{
+    BatchAction, BatchOutcome, BatchRequest, DeletedIngredient, ImportOutcome,
+    Ingredient as IIngredient, IngredientQuery, IngredientSort, NewIngredient, NewRecipe, Page,
+    Recipe, RecipeIngredient, SectionCount, SortField,
+};
+
+/// Errors shared by every call made through the api module.
+#[derive(Debug)]
+pub enum ApiError {
+    /// The request could not be built or handed to the fetch service.
+    Request(String),
+    /// The server answered with a non-success status code.
+    Status(u16),
+    /// The response body was missing or did not match the expected shape.
+    Decode(String),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Request(message) => write!(f, "Could not send request: {}", message),
+            ApiError::Status(code) => write!(f, "Server responded with status {}", code),
+            ApiError::Decode(message) => write!(f, "Could not read response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+pub type ApiCallback<T> = Callback<Result<T, ApiError>>;
+
+fn url(path: &str) -> String {
+    format!("{}{}", config::api_base_url(), path)
+}
+
+/// Sends `request` and unwraps the response envelope before handing the
+/// result to `callback`. The returned task must be kept alive until the
+/// callback fires, otherwise the request is canceled.
+fn send<B, T>(request: Request<B>, callback: ApiCallback<T>) -> Result<FetchTask, ApiError>
+where
+    B: Into<Text>,
+    T: DeserializeOwned + 'static,
+{
+    let handler = Callback::from(
+        move |response: Response<Json<Result<Envelope<T>, anyhow::Error>>>| {
+            let status = response.status();
+            let Json(data) = response.into_body();
+            let result = if !status.is_success() {
+                Err(ApiError::Status(status.as_u16()))
+            } else {
+                data.map(|envelope| envelope.response)
+                    .map_err(|error| ApiError::Decode(error.to_string()))
+            };
+            callback.emit(result)
+        },
+    );
+    FetchService::fetch(request, handler).map_err(|error| ApiError::Request(error.to_string()))
+}
+
+/// Fetches the whole catalog, for pickers that need every ingredient at once.
+pub fn list_ingredients(callback: ApiCallback<Vec<IIngredient>>) -> Result<FetchTask, ApiError> {
+    let callback = callback
+        .reform(|result: Result<Page<IIngredient>, ApiError>| result.map(|page| page.items));
+    search_ingredients(&IngredientQuery::default(), callback)
+}
+
+/// Fetches one page of the ingredients matching `query`.
+pub fn search_ingredients(
+    query: &IngredientQuery,
+    callback: ApiCallback<Page<IIngredient>>,
+) -> Result<FetchTask, ApiError> {
+    let path = format!("/ingredients{}", query.to_query_string());
+    let request = Request::get(url(&path))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+/// Applies one action to many ingredients, reporting what happened to each.
+pub fn batch_ingredients(
+    batch: &BatchRequest,
+    callback: ApiCallback<Vec<BatchOutcome>>,
+) -> Result<FetchTask, ApiError> {
+    let request = Request::post(url("/ingredients/batch"))
+        .header("Content-Type", "application/json")
+        .body(Json(batch))
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+/// Creates every ingredient of `rows`, reporting what happened to each row.
+pub fn import_ingredients(
+    rows: &[NewIngredient],
+    callback: ApiCallback<Vec<ImportOutcome>>,
+) -> Result<FetchTask, ApiError> {
+    let request = Request::post(url("/ingredients/import"))
+        .header("Content-Type", "application/json")
+        .body(Json(&rows))
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn list_deleted_ingredients(
+    callback: ApiCallback<Vec<DeletedIngredient>>,
+) -> Result<FetchTask, ApiError> {
+    let request = Request::get(url("/ingredients/deleted"))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn list_food_groups(callback: ApiCallback<Vec<String>>) -> Result<FetchTask, ApiError> {
+    let request = Request::get(url("/ingredients/groups"))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+/// Counts the ingredients matching `query` in each food group and subgroup.
+pub fn list_sections(
+    query: &IngredientQuery,
+    callback: ApiCallback<Vec<SectionCount>>,
+) -> Result<FetchTask, ApiError> {
+    let path = format!("/ingredients/sections{}", query.to_query_string());
+    let request = Request::get(url(&path))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn get_ingredient(id: i32, callback: ApiCallback<IIngredient>) -> Result<FetchTask, ApiError> {
+    let request = Request::get(url(&format!("/ingredients/{}", id)))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn create_ingredient(
+    data: &NewIngredient,
+    callback: ApiCallback<String>,
+) -> Result<FetchTask, ApiError> {
+    let request = Request::post(url("/ingredients"))
+        .header("Content-Type", "application/json")
+        .body(Json(data))
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn update_ingredient(
+    id: i32,
+    data: &NewIngredient,
+    callback: ApiCallback<IIngredient>,
+) -> Result<FetchTask, ApiError> {
+    let request = Request::put(url(&format!("/ingredients/{}", id)))
+        .header("Content-Type", "application/json")
+        .body(Json(data))
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn delete_ingredient(id: i32, callback: ApiCallback<String>) -> Result<FetchTask, ApiError> {
+    let request = Request::delete(url(&format!("/ingredients/{}", id)))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn list_recipes(callback: ApiCallback<Vec<Recipe>>) -> Result<FetchTask, ApiError> {
+    let request = Request::get(url("/recipes"))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn get_recipe(id: i32, callback: ApiCallback<Recipe>) -> Result<FetchTask, ApiError> {
+    let request = Request::get(url(&format!("/recipes/{}", id)))
+        .body(Nothing)
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
+
+pub fn create_recipe(
+    data: &NewRecipe,
+    callback: ApiCallback<String>,
+) -> Result<FetchTask, ApiError> {
+    let request = Request::post(url("/recipes"))
+        .header("Content-Type", "application/json")
+        .body(Json(data))
+        .map_err(|error| ApiError::Request(error.to_string()))?;
+    send(request, callback)
+}
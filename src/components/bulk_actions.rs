@@ -0,0 +1,214 @@
+use crate::api::{self, ApiError, BatchAction, BatchOutcome, BatchRequest};
+use crate::components::{button_styles, Combobox, Modal};
+use whats_cooking_types::taxonomy::Taxonomy;
+use yew::services::fetch::FetchTask;
+use yew::{html, Callback, Component, ComponentLink, Html, Properties, ShouldRender};
+
+/// Bar of actions applied to every selected ingredient at once, shown while
+/// some are selected, along with what happened to each after the last action.
+pub struct BulkActions {
+    props: Props,
+    state: States,
+    food_group: String,
+    food_subgroup: String,
+    fetch_task: Option<FetchTask>,
+    /// Whether the running action is a delete.
+    deleting: bool,
+    link: ComponentLink<Self>,
+}
+
+pub enum States {
+    Initial,
+    ConfirmingDelete,
+    Running,
+    Done(Vec<BatchOutcome>),
+    Error(ApiError),
+}
+
+pub enum Msg {
+    AttemptDelete,
+    CancelDelete,
+    Run(BatchAction),
+    RunSuccess(Vec<BatchOutcome>),
+    RunError(ApiError),
+    UpdateFoodGroup(String),
+    UpdateFoodSubgroup(String),
+    Dismiss,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// Ids of the selected ingredients.
+    pub selected: Vec<i32>,
+    /// Called with the outcome of every ingredient after an action ran.
+    #[prop_or_default]
+    pub on_done: Callback<Vec<BatchOutcome>>,
+    /// Called with the ids of the ingredients a delete removed, e.g. to offer undoing it.
+    #[prop_or_default]
+    pub on_deleted: Callback<Vec<i32>>,
+    #[prop_or_default]
+    pub on_clear: Callback<()>,
+}
+
+impl BulkActions {
+    fn view_outcomes(&self, outcomes: &[BatchOutcome]) -> Html {
+        let failed: Vec<&BatchOutcome> = outcomes.iter().filter(|o| o.error.is_some()).collect();
+        html! {
+            <div class="mt-2 text-sm">
+                <p class="text-green-600">
+                    {format!("{} of {} ingredients updated.", outcomes.len() - failed.len(), outcomes.len())}
+                </p>
+                <ul class="text-red-600">
+                    {failed.iter().map(|outcome| html! {
+                        <li>{format!("#{}: {}", outcome.id, outcome.error.as_deref().unwrap_or(""))}</li>
+                    }).collect::<Html>()}
+                </ul>
+                <button class="text-indigo-600" onclick=self.link.callback(|_| Msg::Dismiss)>{"Dismiss"}</button>
+            </div>
+        }
+    }
+}
+
+impl Component for BulkActions {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            props,
+            state: States::Initial,
+            food_group: String::new(),
+            food_subgroup: String::new(),
+            fetch_task: None,
+            deleting: false,
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::AttemptDelete => self.state = States::ConfirmingDelete,
+            Msg::CancelDelete | Msg::Dismiss => self.state = States::Initial,
+            Msg::UpdateFoodGroup(value) => {
+                self.food_group = value;
+                self.food_subgroup.clear();
+            }
+            Msg::UpdateFoodSubgroup(value) => self.food_subgroup = value,
+            Msg::Run(action) => {
+                self.deleting = action == BatchAction::Delete;
+                let batch = BatchRequest {
+                    ids: self.props.selected.clone(),
+                    action,
+                };
+                let callback = self.link.callback(|result| match result {
+                    Ok(outcomes) => Msg::RunSuccess(outcomes),
+                    Err(error) => Msg::RunError(error),
+                });
+                match api::batch_ingredients(&batch, callback) {
+                    Ok(task) => {
+                        // store the task so it isn't canceled immediately
+                        self.fetch_task = Some(task);
+                        self.state = States::Running;
+                    }
+                    Err(error) => self.state = States::Error(error),
+                }
+            }
+            Msg::RunSuccess(outcomes) => {
+                self.fetch_task = None;
+                self.props.on_done.emit(outcomes.clone());
+                if self.deleting {
+                    let deleted = outcomes.iter().filter(|o| o.error.is_none()).map(|o| o.id);
+                    self.props.on_deleted.emit(deleted.collect());
+                }
+                self.state = States::Done(outcomes);
+            }
+            Msg::RunError(error) => {
+                self.fetch_task = None;
+                self.state = States::Error(error);
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let count = self.props.selected.len();
+        let outcome = match &self.state {
+            States::Done(outcomes) => self.view_outcomes(outcomes),
+            States::Error(error) => html! { <p class="mt-2 text-sm text-red-600">{error}</p> },
+            _ => html! {},
+        };
+        if count == 0 {
+            return outcome;
+        }
+        let taxonomy = Taxonomy::bundled();
+        let subgroups = taxonomy
+            .group(&self.food_group)
+            .map(|group| group.subgroups.clone())
+            .unwrap_or_default();
+        let food_group = self.food_group.clone();
+        let food_subgroup = Some(self.food_subgroup.clone()).filter(|s| !s.trim().is_empty());
+        html! {
+            <div class="mb-4 p-2 rounded-md bg-indigo-50">
+                <div class="flex items-end space-x-2">
+                    <p class="pb-2">{format!("{} selected", count)}</p>
+                    <button
+                        class=button_styles
+                        disabled=matches!(self.state, States::Running)
+                        onclick=self.link.callback(|_| Msg::AttemptDelete)
+                    >
+                        {"Delete"}
+                    </button>
+                    <Combobox
+                        class="w-1/4"
+                        name="bulk-group"
+                        id="bulk-group"
+                        label="Food group"
+                        value=&self.food_group
+                        options=taxonomy.groups.iter().map(|group| group.name.clone()).collect::<Vec<_>>()
+                        on_change=self.link.callback(Msg::UpdateFoodGroup)
+                    />
+                    <Combobox
+                        class="w-1/4"
+                        name="bulk-subgroup"
+                        id="bulk-subgroup"
+                        label="Food subgroup (kept when empty)"
+                        value=&self.food_subgroup
+                        options=subgroups
+                        on_change=self.link.callback(Msg::UpdateFoodSubgroup)
+                    />
+                    <button
+                        class=button_styles
+                        disabled=self.food_group.trim().is_empty() || matches!(self.state, States::Running)
+                        onclick=self.link.callback(move |_| Msg::Run(BatchAction::SetFoodGroup {
+                            food_group: food_group.clone(),
+                            food_subgroup: food_subgroup.clone(),
+                        }))
+                    >
+                        {"Change group"}
+                    </button>
+                    <button class="pb-2 text-indigo-600" onclick=self.props.on_clear.reform(|_| ())>
+                        {"Clear selection"}
+                    </button>
+                </div>
+                {match self.state {
+                    States::Running => html! { <p class="mt-2 text-sm">{"Working..."}</p> },
+                    States::ConfirmingDelete => html! {
+                        <Modal
+                            title={String::from("Hold up!")}
+                            description={format!("Are you sure you want to delete {} ingredients?", count)}
+                            on_cancel=self.link.callback(|_| Msg::CancelDelete)
+                            on_confirm=self.link.callback(|_| Msg::Run(BatchAction::Delete))
+                        />
+                    },
+                    _ => html! {},
+                }}
+                {outcome}
+            </div>
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::export::Format;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+use yew::{html, Callback, Component, ComponentLink, Html, Properties, ShouldRender};
+
+/// How long a downloaded blob is kept, in milliseconds. Revoking it right
+/// after the click cancels the download in some browsers.
+const REVOKE_DELAY_MS: i32 = 60_000;
+
+/// Buttons picking an export format. The page generates the file from its
+/// data only once one is clicked and saves it with [`download`].
+pub struct ExportMenu {
+    props: Props,
+}
+
+pub enum Msg {}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// Called with the format of the clicked button.
+    pub on_export: Callback<Format>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// Saves `contents` as `file_name` with the extension of `format`, through a
+/// temporary link to a blob that is freed a minute later.
+pub fn download(file_name: &str, format: Format, contents: &str) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("no window")?;
+    let document = window.document().ok_or("no document")?;
+    let options = BlobPropertyBag::new();
+    options.set_type(format.mime_type());
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+    let anchor: HtmlAnchorElement = document.create_element("a")?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(&format!("{}.{}", file_name, format.extension()));
+    anchor.click();
+    // a browser timer rather than a `TimeoutTask`, which would have to be
+    // kept alive by the page for the blob to ever be freed
+    let revoke = Closure::once_into_js(move || {
+        if let Err(error) = Url::revoke_object_url(&url) {
+            log::error!("could not free export: {:?}", error);
+        }
+    });
+    window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        revoke.unchecked_ref(),
+        REVOKE_DELAY_MS,
+    )?;
+    Ok(())
+}
+
+impl Component for ExportMenu {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="flex items-center space-x-2 text-sm">
+                <span class="text-gray-600">{"Export as"}</span>
+                {Format::ALL.iter().map(|&format| html! {
+                    <button
+                        class="text-indigo-600 hover:underline"
+                        disabled=self.props.disabled
+                        onclick=self.props.on_export.reform(move |_| format)
+                    >
+                        {format.label()}
+                    </button>
+                }).collect::<Html>()}
+            </div>
+        }
+    }
+}
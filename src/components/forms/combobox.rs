@@ -0,0 +1,237 @@
+use crate::suggestions::{matched_suggestions, suggestions, Suggestion};
+use web_sys::KeyboardEvent;
+use yew::{html, Callback, Component, ComponentLink, Html, InputData, Properties, ShouldRender};
+
+/// Most suggestions listed at once.
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Text input suggesting existing values as the user types, picked with the
+/// mouse or the arrow and enter keys.
+///
+/// Only picked values are passed to `on_change`, so a new value has to be
+/// added on purpose through the "Create" suggestion. Typing something and
+/// leaving the field goes back to the current value.
+pub struct Combobox {
+    props: Props,
+    /// Text in the input, which may not have been picked yet.
+    query: String,
+    is_open: bool,
+    /// Suggestion highlighted with the arrow keys.
+    active: Option<usize>,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    Input(String),
+    Open,
+    Close,
+    Move(isize),
+    PickActive,
+    Pick(String),
+    Ignore,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub name: String,
+    pub id: String,
+    pub label: String,
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Current value.
+    #[prop_or_default]
+    pub value: String,
+    /// Existing values to suggest.
+    #[prop_or_default]
+    pub options: Vec<String>,
+    /// Whether values outside of `options` can be created.
+    #[prop_or(true)]
+    pub allow_create: bool,
+    /// Whether `options` are narrowed down to what is typed. Turned off when
+    /// the parent already matched them, e.g. with a search on the server.
+    #[prop_or(true)]
+    pub filter: bool,
+    /// Called with what is typed, e.g. to fetch the matching options.
+    #[prop_or_default]
+    pub on_input: Callback<String>,
+    pub on_change: Callback<String>,
+}
+
+impl Combobox {
+    fn suggestions(&self) -> Vec<Suggestion> {
+        let suggest = if self.props.filter {
+            suggestions
+        } else {
+            matched_suggestions
+        };
+        suggest(
+            &self.props.options,
+            &self.query,
+            MAX_SUGGESTIONS,
+            self.props.allow_create,
+        )
+    }
+
+    fn pick(&mut self, value: String) {
+        // kept until the parent passes the value back, so closing doesn't undo the pick
+        self.props.value = value.clone();
+        self.query = value.clone();
+        self.is_open = false;
+        self.active = None;
+        self.props.on_change.emit(value);
+    }
+}
+
+impl Component for Combobox {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            query: props.value.clone(),
+            props,
+            is_open: false,
+            active: None,
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Input(value) => {
+                self.props.on_input.emit(value.clone());
+                self.query = value;
+                self.is_open = true;
+                self.active = None;
+            }
+            Msg::Open => self.is_open = true,
+            Msg::Close => {
+                // what was typed without picking it is dropped
+                self.query = self.props.value.clone();
+                self.is_open = false;
+                self.active = None;
+            }
+            Msg::Move(step) => {
+                let count = self.suggestions().len() as isize;
+                if count == 0 {
+                    return false;
+                }
+                self.is_open = true;
+                let next = match self.active {
+                    Some(active) => (active as isize + step).rem_euclid(count),
+                    None if step > 0 => 0,
+                    None => count - 1,
+                };
+                self.active = Some(next as usize);
+            }
+            Msg::PickActive => {
+                let suggestions = self.suggestions();
+                let picked = match self.active {
+                    Some(active) => suggestions.get(active),
+                    // enter on an exact match picks it without moving to it first
+                    None => suggestions
+                        .iter()
+                        .find(|s| s.value().eq_ignore_ascii_case(self.query.trim())),
+                };
+                match picked {
+                    Some(suggestion) => {
+                        let value = suggestion.value().to_string();
+                        self.pick(value);
+                    }
+                    None => return false,
+                }
+            }
+            Msg::Pick(value) => self.pick(value),
+            Msg::Ignore => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.value != self.props.value {
+            self.query = props.value.clone();
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let class = match &self.props.class {
+            Some(c) => c,
+            None => "",
+        };
+        let list_id = format!("{}-suggestions", self.props.id);
+        let suggestions = if self.is_open {
+            self.suggestions()
+        } else {
+            vec![]
+        };
+        html! {
+            <div class={format!("flex flex-col h-auto relative {}", class)}>
+                <label
+                    for=&self.props.id
+                    class="ml-2 mb-2 text-sm text-gray-600 dark:text-gray-400"
+                >
+                    {&self.props.label}
+                </label>
+                <input
+                    id=&self.props.id
+                    name=&self.props.name
+                    class="px-2 py-2 border-2 rounded-md border-gray-200"
+                    type="text"
+                    role="combobox"
+                    autocomplete="off"
+                    aria-expanded=(!suggestions.is_empty()).to_string()
+                    aria-controls=&list_id
+                    value=&self.query
+                    oninput=self.link.callback(|event: InputData| Msg::Input(event.value))
+                    onfocus=self.link.callback(|_| Msg::Open)
+                    onblur=self.link.callback(|_| Msg::Close)
+                    onkeydown=self.link.callback(|event: KeyboardEvent| {
+                        let msg = match event.key().as_str() {
+                            "ArrowDown" => Msg::Move(1),
+                            "ArrowUp" => Msg::Move(-1),
+                            "Enter" => Msg::PickActive,
+                            "Escape" => Msg::Close,
+                            _ => return Msg::Ignore,
+                        };
+                        event.prevent_default();
+                        msg
+                    })
+                />
+                {if suggestions.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <ul
+                            id=&list_id
+                            role="listbox"
+                            class="absolute z-10 w-full top-full mt-1 bg-white border-2 rounded-md border-gray-200 shadow"
+                        >
+                            {suggestions.iter().enumerate().map(|(index, suggestion)| {
+                                let value = suggestion.value().to_string();
+                                let active = self.active == Some(index);
+                                html! {
+                                    <li
+                                        role="option"
+                                        aria-selected=active.to_string()
+                                        class=if active { "px-2 py-1 cursor-pointer bg-indigo-100" } else { "px-2 py-1 cursor-pointer" }
+                                        // mousedown comes before the input loses focus and closes the list
+                                        onmousedown=self.link.callback(move |_| Msg::Pick(value.clone()))
+                                    >
+                                        {match suggestion {
+                                            Suggestion::Existing(value) => html! { {value} },
+                                            Suggestion::Create(value) => html! {
+                                                <span class="text-indigo-600">{format!("Create \"{}\"", value)}</span>
+                                            },
+                                        }}
+                                    </li>
+                                }
+                            }).collect::<Html>()}
+                        </ul>
+                    }
+                }}
+            </div>
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use yew::{html, Component, ComponentLink, Html, Properties, ShouldRender};
 
 pub struct Input {
-    value: String,
     props: Props,
 }
 
@@ -18,6 +17,9 @@ pub struct Props {
     pub input_type: String,
     #[prop_or_default]
     pub class: Option<String>,
+    /// Initial value, e.g. when editing an existing record.
+    #[prop_or_default]
+    pub value: String,
     pub on_change: yew::Callback<yew::InputData>,
 }
 
@@ -26,18 +28,16 @@ impl Component for Input {
     type Properties = Props;
 
     fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
-        Self {
-            props,
-            value: String::from(""),
-        }
+        Self { props }
     }
 
     fn update(&mut self, _msg: Self::Message) -> ShouldRender {
         true
     }
 
-    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
-        false
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
     }
 
     fn view(&self) -> Html {
@@ -58,7 +58,7 @@ impl Component for Input {
                     name=&self.props.name
                     class="px-2 py-2 border-2 rounded-md border-gray-200"
                     type=&self.props.input_type
-                    value=&self.value
+                    value=&self.props.value
                     oninput=&self.props.on_change />
             </div>
         }
@@ -1,3 +1,7 @@
+mod combobox;
 mod input;
+mod textarea;
 
+pub use combobox::Combobox;
 pub use input::Input;
+pub use textarea::TextArea;
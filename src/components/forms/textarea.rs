@@ -0,0 +1,61 @@
+use yew::{html, Component, ComponentLink, Html, Properties, ShouldRender};
+
+/// Multi-line counterpart of [`Input`](super::Input).
+pub struct TextArea {
+    props: Props,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub name: String,
+    pub label: String,
+    #[prop_or(4)]
+    pub rows: u32,
+    #[prop_or_default]
+    pub class: Option<String>,
+    #[prop_or_default]
+    pub value: String,
+    pub on_change: yew::Callback<yew::InputData>,
+}
+
+impl Component for TextArea {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let class = match &self.props.class {
+            Some(c) => c,
+            None => "",
+        };
+        html! {
+            <div class={format!("flex flex-col h-auto relative {}", class)}>
+                <label
+                    for=&self.props.name
+                    class="ml-2 mb-2 text-sm text-gray-600 dark:text-gray-400"
+                >
+                    {&self.props.label}
+                </label>
+                <textarea
+                    id=&self.props.name
+                    name=&self.props.name
+                    rows=self.props.rows.to_string()
+                    class="px-2 py-2 border-2 rounded-md border-gray-200"
+                    value=&self.props.value
+                    oninput=&self.props.on_change />
+            </div>
+        }
+    }
+}
@@ -63,6 +63,11 @@ impl Component for Header {
         } else {
             " "
         };
+        let active_classes_pantry = if is_active("pantry") {
+            "text-blue-300"
+        } else {
+            " "
+        };
         // self.current_location.contains("recipe")
         log::info!("in vuiew: {:?}", self.current_location);
         html! {
@@ -77,6 +82,9 @@ impl Component for Header {
                 <AppAnchor classes=active_classes_ingredients route=AppRoutes::IngredientsPage>
                     {"Ingredient"}
                 </AppAnchor>
+                <AppAnchor classes=active_classes_pantry route=AppRoutes::PantryPage>
+                    {"What can I cook?"}
+                </AppAnchor>
             </nav>
           </header>
         }
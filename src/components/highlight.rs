@@ -0,0 +1,20 @@
+use std::ops::Range;
+use whats_cooking_types::search::merge_ranges;
+use yew::{html, Html};
+
+/// Renders `text`, wrapping the byte `ranges` that matched a search in `<mark>`.
+pub fn highlighted(text: &str, ranges: &[Range<usize>]) -> Html {
+    let mut parts = vec![];
+    let mut last = 0;
+    for range in merge_ranges(ranges) {
+        // ranges come from the search over the same text, skip any that don't fit it anymore
+        if range.end > text.len() || range.start < last {
+            continue;
+        }
+        parts.push(html! { {&text[last..range.start]} });
+        parts.push(html! { <mark>{&text[range.clone()]}</mark> });
+        last = range.end;
+    }
+    parts.push(html! { {&text[last..]} });
+    parts.into_iter().collect::<Html>()
+}
@@ -1,12 +1,10 @@
-use crate::components::Modal;
-use serde::Deserialize;
-use yew::format::Json;
-use yew::format::Nothing;
-use yew::services::{
-    fetch::{FetchTask, Request, Response},
-    FetchService,
-};
-use yew::{html, Component, ComponentLink, Html, Properties, ShouldRender};
+use crate::api::{self, IIngredient};
+use crate::components::{highlighted, Modal, NewIngredientsForm};
+use crate::routes::{AppAnchor, AppRoutes};
+use whats_cooking_types::search::Highlights;
+use whats_cooking_types::taxonomy::Taxonomy;
+use yew::services::fetch::FetchTask;
+use yew::{html, Callback, Component, ComponentLink, Html, Properties, ShouldRender};
 
 pub struct Ingredient {
     props: Props,
@@ -18,19 +16,18 @@ pub struct Ingredient {
 
 pub enum States {
     Initial,
+    Editing,
     ConfirmingDelete,
     Deleting,
     Deleted,
 }
 
-#[derive(Deserialize)]
-struct DeleteResponse {
-    response: String,
-}
-
 #[derive(PartialEq)]
 pub enum Msg {
     Toggle,
+    StartEdit,
+    CancelEdit,
+    EditSaved(IIngredient),
     AttemptConfirmation,
     DeleteConfirmationCanceled,
     DeleteIngredient,
@@ -40,12 +37,31 @@ pub enum Msg {
 
 #[derive(Properties, Clone)]
 pub struct Props {
-    pub id: i32,
+    pub ingredient: IIngredient,
+    /// Called with the saved ingredient after an edit so the list can update in place.
+    #[prop_or_default]
+    pub on_update: Callback<IIngredient>,
+    /// Parts of the ingredient matching the current search.
+    #[prop_or_default]
+    pub highlights: Highlights,
+    /// Called with the ingredient once it was deleted, e.g. to offer undoing it.
+    #[prop_or_default]
+    pub on_deleted: Callback<IIngredient>,
+    #[prop_or_default]
+    pub selected: bool,
+    /// Shows a checkbox selecting the ingredient for bulk actions when set.
     #[prop_or_default]
-    pub name: String,
-    pub group: String,
+    pub on_select: Option<Callback<()>>,
+    /// Called with whether the ingredient is open, edited or being deleted,
+    /// e.g. so a virtual list keeps it mounted meanwhile.
     #[prop_or_default]
-    pub description: Option<String>,
+    pub on_busy: Callback<bool>,
+}
+
+impl Ingredient {
+    fn is_busy(&self) -> bool {
+        self.is_open || !matches!(self.state, States::Initial | States::Deleted)
+    }
 }
 
 impl Component for Ingredient {
@@ -63,49 +79,53 @@ impl Component for Ingredient {
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let was_busy = self.is_busy();
         if msg == Msg::Toggle {
             self.is_open = !self.is_open;
-            return true;
         }
         match self.state {
             States::Initial => match msg {
+                Msg::StartEdit => self.state = States::Editing,
                 Msg::AttemptConfirmation => self.state = States::ConfirmingDelete,
                 _ => {}
             },
+            States::Editing => match msg {
+                Msg::CancelEdit => self.state = States::Initial,
+                Msg::EditSaved(ingredient) => {
+                    self.state = States::Initial;
+                    self.props.on_update.emit(ingredient);
+                }
+                _ => {}
+            },
             States::ConfirmingDelete => match msg {
                 Msg::DeleteConfirmationCanceled => self.state = States::Initial,
                 Msg::DeleteIngredient => {
-                    log::info!("deleting ingredients: {}", self.props.id);
-                    let request = Request::delete(format!(
-                        "http://localhost:8082/ingredients/{}",
-                        self.props.id
-                    ))
-                    .body(Nothing)
-                    .expect("Could not build request.");
-                    // 2. construct a callback
-                    let callback = self.link.callback(
-                        |response: Response<Json<Result<DeleteResponse, anyhow::Error>>>| {
-                            let Json(data) = response.into_body();
-                            match data {
-                                Ok(_) => Msg::DeleteSuccess,
-                                Err(_) => Msg::DeleteError,
-                            }
-                        },
-                    );
-                    // 3. pass the request and callback to the fetch service
-                    let task =
-                        FetchService::fetch(request, callback).expect("failed to start request");
-                    // 4. store the task so it isn't canceled immediately
-                    self.fetch_task = Some(task);
-                    // we want to redraw so that the page displays a 'fetching...' message to the user
-                    // so return 'true'
-                    self.state = States::Deleting;
+                    log::info!("deleting ingredients: {}", self.props.ingredient.id);
+                    let callback = self.link.callback(|result| match result {
+                        Ok(_) => Msg::DeleteSuccess,
+                        Err(error) => {
+                            log::error!("failed to delete ingredient: {}", error);
+                            Msg::DeleteError
+                        }
+                    });
+                    match api::delete_ingredient(self.props.ingredient.id, callback) {
+                        Ok(task) => {
+                            // store the task so it isn't canceled immediately
+                            self.fetch_task = Some(task);
+                            self.state = States::Deleting;
+                        }
+                        Err(error) => {
+                            log::error!("failed to delete ingredient: {}", error);
+                            self.state = States::Initial;
+                        }
+                    }
                 }
                 _ => {}
             },
             States::Deleting => match msg {
                 Msg::DeleteSuccess => {
                     self.state = States::Deleted;
+                    self.props.on_deleted.emit(self.props.ingredient.clone());
                     self.link.send_message(Msg::DeleteSuccess);
                 }
                 Msg::DeleteError => {
@@ -116,46 +136,64 @@ impl Component for Ingredient {
             },
             States::Deleted => {}
         }
+        if self.is_busy() != was_busy {
+            self.props.on_busy.emit(self.is_busy());
+        }
         true
     }
 
-    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
-        false
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn destroy(&mut self) {
+        if self.is_busy() {
+            self.props.on_busy.emit(false);
+        }
     }
 
     fn view(&self) -> Html {
-        let food_emoji = match &self.props.group[..] {
-            "Fruits" => "🍌",
-            "Vegetables" => "🥦",
-            "Herbs and Spices" => "🧂",
-            "Nuts" => "🥜",
-            "Cereals and cereal products" => "🌾",
-            "Gourds" => "🥒",
-            "Soy" => "🌾",
-            "Pulses" => "🌾",
-            "Aquatic foods" => "🐟",
-            "Animal foods" => "🥩",
-            "Beverages" => "🍹",
-            "Confectioneries" => "🍬",
-            "Baking goods" => "🍰",
-            "Milk and milk products" => "🧀",
-            "Fats and oils" => "🛢",
-            "Cocoa and cocoa products" => "🍫",
-            "Dishes" => "🍴",
-            "Snack foods" => "🍱",
-            "Teas" => "☕",
-            "Eggs" => "🥚",
-            "Baby foods" => "👶",
-            "Coffee and coffee products" => "☕",
-            _ => "❌",
+        let ingredient = &self.props.ingredient;
+        let highlights = &self.props.highlights;
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let group = match &ingredient.food_group {
+            Some(v) => v,
+            None => ".",
         };
+        let taxonomy = Taxonomy::bundled();
+        let food_emoji = taxonomy.icon(ingredient.food_group.as_deref());
+        let food_color = taxonomy.color(ingredient.food_group.as_deref());
         return match &self.state {
+            States::Editing => html! {
+                <div class="container flex flex-col m-auto p-2" >
+                    <NewIngredientsForm
+                        ingredient=ingredient.clone()
+                        on_saved=self.link.callback(Msg::EditSaved)
+                        on_cancel=self.link.callback(|_| Msg::CancelEdit)
+                    />
+                </div>
+            },
             States::Initial | States::Deleting | States::ConfirmingDelete => html! {
                 <div class="container flex flex-col m-auto p-2" >
                   <div class="flex justify-between">
                       <div class="flex">
-                          <div class="mr-2 w-6 text-center">{food_emoji}</div>
-                          <h3 class="p4">{&self.props.name}</h3>
+                          {match &self.props.on_select {
+                              Some(on_select) => html! {
+                                  <input
+                                      type="checkbox"
+                                      class="mr-2 mt-1"
+                                      title="select"
+                                      checked=self.props.selected
+                                      onclick=on_select.reform(|_| ())
+                                  />
+                              },
+                              None => html! {},
+                          }}
+                          <div class="mr-2 w-6 text-center border-l-4" style=format!("border-color: {}", food_color) title=group>{food_emoji}</div>
+                          <AppAnchor route=AppRoutes::IngredientDetailPage(ingredient.id)>
+                              <h3 class="p4 hover:underline">{highlighted(&ingredient.name, &highlights.name)}</h3>
+                          </AppAnchor>
                       </div>
                       <button onclick=self.link.callback(|_| Msg::Toggle)>
                           {match &self.is_open {
@@ -168,25 +206,27 @@ impl Component for Ingredient {
                       true => html! {
                           <div class="mt-2 space-y-1 flex justify-between">
                               <div>
-                                  <p>{"type: "}{&self.props.group}</p>
-                                  <p>{"description: "}{match &self.props.description {
-                                      Some(s) => s,
-                                      None => ""
-                                  }}</p>
+                                  <p>{"type: "}{highlighted(group, &highlights.food_group)}</p>
+                                  <p>{"subgroup: "}{highlighted(&optional(&ingredient.food_subgroup), &highlights.food_subgroup)}</p>
+                                  <p>{"scientific name: "}{highlighted(&optional(&ingredient.name_scientific), &highlights.name_scientific)}</p>
+                                  <p>{"description: "}{highlighted(&optional(&ingredient.description), &highlights.description)}</p>
                               </div>
                               {match &self.state {
                                   States::Initial => html! {
-                                      <button class="flex" title="delete" onclick=&self.link.callback(|_| Msg::AttemptConfirmation)>{"❌"}</button>
+                                      <div class="flex space-x-2">
+                                          <button class="flex" title="edit" onclick=&self.link.callback(|_| Msg::StartEdit)>{"✏️"}</button>
+                                          <button class="flex" title="delete" onclick=&self.link.callback(|_| Msg::AttemptConfirmation)>{"❌"}</button>
+                                      </div>
                                   },
                                   States::ConfirmingDelete => html! {
                                     <Modal
                                         title={String::from("Hold up!")}
-                                        description={format!("Are you sure you want to delete the ingredient {}?", &self.props.name)}
+                                        description={format!("Are you sure you want to delete the ingredient {}?", &ingredient.name)}
                                         on_cancel=&self.link.callback(move |_| Msg::DeleteConfirmationCanceled)
                                         on_confirm=&self.link.callback(move |_| Msg::DeleteIngredient)
                                     />
                                   },
-                                  States::Deleting | States::Deleted => html! {
+                                  States::Editing | States::Deleting | States::Deleted => html! {
                                       <div>{'🍤'}</div>
                                   }
                               }}
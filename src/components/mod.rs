@@ -1,17 +1,27 @@
+mod bulk_actions;
+mod export_menu;
 mod forms;
 mod header;
+mod highlight;
 mod ingredient;
 mod layout;
 mod modal;
 mod newIngredientsForm;
 mod recipes;
 mod styles;
+mod toast;
+mod virtual_list;
 
-pub use forms::Input;
+pub use bulk_actions::BulkActions;
+pub use export_menu::{download, ExportMenu};
+pub use forms::{Combobox, Input, TextArea};
 pub use header::Header;
+pub use highlight::highlighted;
 pub use ingredient::Ingredient;
 pub use layout::Layout;
 pub use modal::Modal;
 pub use newIngredientsForm::NewIngredientsForm;
-pub use recipes::NewRecipesForm;
+pub use recipes::{NewRecipesForm, RecipeCard};
 pub use styles::button_styles;
+pub use toast::Toast;
+pub use virtual_list::{RenderRow, VirtualList};
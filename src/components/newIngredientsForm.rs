@@ -1,34 +1,18 @@
+use crate::api::{self, ApiError, IIngredient, NewIngredient};
 use crate::components::styles::button_styles;
-use crate::components::Input;
-use crate::yew::format::Json;
+use crate::components::{Combobox, Input};
 use crate::yew::InputData;
-use serde::{Deserialize, Serialize};
+use whats_cooking_types::taxonomy::Taxonomy;
 use yew::{
-    html,
-    services::{
-        fetch::{FetchTask, Request, Response},
-        FetchService,
-    },
-    Children, Component, ComponentLink, Html, Properties, ShouldRender,
+    html, services::fetch::FetchTask, Callback, Children, Component, ComponentLink, Html,
+    Properties, ShouldRender,
 };
 
-#[derive(Deserialize)]
-struct PostResponse {
-    response: String,
-}
-
-#[derive(Serialize, Default, Deserialize, Clone, Debug)]
-pub struct FormData {
-    name: String,
-    food_group: String,
-    decription: Option<String>,
-    name_scientific: Option<String>,
-    food_subgroup: Option<String>,
-}
-
+/// Form for creating an ingredient, or editing one when `ingredient` is passed.
 pub struct NewIngredientsForm {
     link: ComponentLink<Self>,
-    form_data: FormData,
+    props: Props,
+    form_data: NewIngredient,
     state: States,
     fetch_task: Option<FetchTask>,
 }
@@ -47,38 +31,86 @@ pub enum States {
     Invalid,
     Submitting,
     Success,
-    Error(anyhow::Error),
+    Error(ApiError),
 }
 
 pub enum Msg {
     UpdateFormField(FormFieldName, String),
     Submit,
     StartSubmit,
-    SubmitSuccess,
-    SubmitError(anyhow::Error),
+    /// Carries the saved ingredient when an existing one was edited.
+    SubmitSuccess(Option<IIngredient>),
+    SubmitError(ApiError),
+    Cancel,
 }
 
 #[derive(Properties, Clone)]
 pub struct Props {
     #[prop_or_default]
     pub children: Children,
+    /// Ingredient to edit. The form creates a new ingredient when this is `None`.
+    #[prop_or_default]
+    pub ingredient: Option<IIngredient>,
+    #[prop_or_default]
+    pub on_saved: Callback<IIngredient>,
+    #[prop_or_default]
+    pub on_cancel: Callback<()>,
+}
+
+fn form_data_for(ingredient: &IIngredient) -> NewIngredient {
+    NewIngredient {
+        name: ingredient.name.clone(),
+        food_group: ingredient.food_group.clone().unwrap_or_default(),
+        description: ingredient.description.clone(),
+        name_scientific: ingredient.name_scientific.clone(),
+        food_subgroup: ingredient.food_subgroup.clone(),
+    }
+}
+
+impl NewIngredientsForm {
+    /// Tells whether the group and subgroup typed are known to the taxonomy,
+    /// so new ones are only added on purpose.
+    fn view_taxonomy_hint(&self) -> Html {
+        let typed_group = self.form_data.food_group.trim();
+        if typed_group.is_empty() {
+            return html! {};
+        }
+        let typed_subgroup = self
+            .form_data
+            .food_subgroup
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or("");
+        let hint = match Taxonomy::bundled().group(typed_group) {
+            None => format!("\"{}\" is a new food group.", typed_group),
+            Some(group)
+                if !typed_subgroup.is_empty() && group.subgroup(typed_subgroup).is_none() =>
+            {
+                format!(
+                    "\"{}\" is a new subgroup of {}.",
+                    typed_subgroup, group.name
+                )
+            }
+            Some(_) => return html! {},
+        };
+        html! { <p class="ml-2 mb-2 text-sm text-yellow-600">{hint}</p> }
+    }
 }
 
 impl Component for NewIngredientsForm {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         Self {
             fetch_task: None,
             state: States::Initial,
-            form_data: FormData {
-                name: String::from(""),
-                food_group: String::from(""),
-                food_subgroup: None,
-                decription: None,
-                name_scientific: None,
-            },
+            form_data: props
+                .ingredient
+                .as_ref()
+                .map(form_data_for)
+                .unwrap_or_default(),
+            props,
             link,
         }
     }
@@ -90,7 +122,7 @@ impl Component for NewIngredientsForm {
                     FormFieldName::Name => self.form_data.name = input_data,
                     FormFieldName::FoodGroup => self.form_data.food_group = input_data,
                     FormFieldName::FoodSubgroup => self.form_data.food_subgroup = Some(input_data),
-                    FormFieldName::Description => self.form_data.decription = Some(input_data),
+                    FormFieldName::Description => self.form_data.description = Some(input_data),
                     FormFieldName::NameScientific => {
                         self.form_data.name_scientific = Some(input_data)
                     }
@@ -99,6 +131,7 @@ impl Component for NewIngredientsForm {
                     log::info!("data: {:?}", self.form_data);
 
                     if self.form_data.name != "" && self.form_data.food_group != "" {
+                        Taxonomy::bundled().canonicalize(&mut self.form_data);
                         self.state = States::Submitting
                     } else {
                         self.state = States::Invalid
@@ -111,62 +144,53 @@ impl Component for NewIngredientsForm {
                     // if invalid do
                     // self.state = States::Invalid
                 }
+                Msg::Cancel => self.props.on_cancel.emit(()),
                 _ => {}
             },
             States::Submitting => match msg {
                 Msg::StartSubmit => {
                     // do the fetch
-                    log::info!("sending post request");
-                    let request = Request::post("http://localhost:8082/ingredients")
-                        .header("Content-Type", "application/json")
-                        .body(Json(&self.form_data))
-                        .expect("Could not build request.");
-                    // 2. construct a callback
-                    let callback = self.link.callback(
-                        |response: Response<Json<Result<PostResponse, anyhow::Error>>>| {
-                            let Json(data) = response.into_body();
-                            // log::info!("got data {:?}", data);
-                            match data {
-                                Ok(_) => Msg::SubmitSuccess,
+                    let task = match &self.props.ingredient {
+                        Some(ingredient) => {
+                            log::info!("sending put request");
+                            let callback = self.link.callback(|result| match result {
+                                Ok(ingredient) => Msg::SubmitSuccess(Some(ingredient)),
                                 Err(error) => Msg::SubmitError(error),
-                            }
-                        },
-                    );
-                    // 3. pass the request and callback to the fetch service
-                    let task =
-                        FetchService::fetch(request, callback).expect("failed to start request");
-                    // 4. store the task so it isn't canceled immediately
-                    self.fetch_task = Some(task);
-                    // we want to redraw so that the page displays a 'fetching...' message to the user
-                    // so return 'true'
+                            });
+                            api::update_ingredient(ingredient.id, &self.form_data, callback)
+                        }
+                        None => {
+                            log::info!("sending post request");
+                            let callback = self.link.callback(|result| match result {
+                                Ok(_) => Msg::SubmitSuccess(None),
+                                Err(error) => Msg::SubmitError(error),
+                            });
+                            api::create_ingredient(&self.form_data, callback)
+                        }
+                    };
+                    match task {
+                        // store the task so it isn't canceled immediately
+                        Ok(task) => self.fetch_task = Some(task),
+                        Err(error) => self.state = States::Error(error),
+                    }
+                }
+                Msg::SubmitSuccess(saved) => {
+                    self.state = States::Success;
+                    if let Some(ingredient) = saved {
+                        self.props.on_saved.emit(ingredient);
+                    }
                 }
-                Msg::SubmitSuccess => self.state = States::Success,
                 Msg::SubmitError(error) => self.state = States::Error(error),
                 _ => {}
             },
-            States::Success => {
-                self.form_data = FormData {
-                    name: String::from(""),
-                    food_group: String::from(""),
-                    food_subgroup: None,
-                    decription: None,
-                    name_scientific: None,
-                }
-            }
-            States::Error(_) => {
-                self.form_data = FormData {
-                    name: String::from(""),
-                    food_group: String::from(""),
-                    food_subgroup: None,
-                    decription: None,
-                    name_scientific: None,
-                }
-            }
+            States::Success => self.form_data = NewIngredient::default(),
+            States::Error(_) => self.form_data = NewIngredient::default(),
         }
         true
     }
 
-    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
         false
     }
 
@@ -176,6 +200,21 @@ impl Component for NewIngredientsForm {
                 .link
                 .callback(move |event: InputData| Msg::UpdateFormField(field, event.value));
         };
+        // Ids have to stay unique when an edit form is open next to the new ingredient form.
+        let id = |field: &str| match &self.props.ingredient {
+            Some(ingredient) => format!("edit-{}-{}", ingredient.id, field),
+            None => String::from(field),
+        };
+        let handle_pick = |field: FormFieldName| {
+            self.link
+                .callback(move |value: String| Msg::UpdateFormField(field, value))
+        };
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let taxonomy = Taxonomy::bundled();
+        let subgroups = taxonomy
+            .group(&self.form_data.food_group)
+            .map(|group| group.subgroups.clone())
+            .unwrap_or_default();
 
         html! {
             <div class="my-6 w-full">
@@ -186,29 +225,35 @@ impl Component for NewIngredientsForm {
                     States::Success => html! {<div>{"Success"}</div> },
                     States::Error(errorMessage) => html! { <h2>{errorMessage}</h2> }
                 }}</h1>
-                <h3 class="mb-4 pl-2 uppercase tracking-wide text-sm text-indigo-600 font-bold">{"New Ingredient"}</h3>
+                <h3 class="mb-4 pl-2 uppercase tracking-wide text-sm text-indigo-600 font-bold">{match &self.props.ingredient {
+                    Some(_) => "Edit Ingredient",
+                    None => "New Ingredient",
+                }}</h3>
                 <div class="flex mb-4 items-center space-x-2 w-full mt-2">
                     <Input
                         class="w-1/2"
-                        name="name"
-                        id="name"
+                        name=id("name")
+                        id=id("name")
+                        value=&self.form_data.name
                         label="Ingredient name"
                         input_type="text"
                         on_change=handle_change(FormFieldName::Name)
                     />
-                    <Input
+                    <Combobox
                         class="w-1/2"
-                        name="group"
-                        id="group"
+                        name=id("group")
+                        id=id("group")
+                        value=&self.form_data.food_group
                         label="Ingredient food group"
-                        input_type="text"
-                        on_change=handle_change(FormFieldName::FoodGroup)
+                        options=taxonomy.groups.iter().map(|group| group.name.clone()).collect::<Vec<_>>()
+                        on_change=handle_pick(FormFieldName::FoodGroup)
                     />
                 </div>
 
                 <Input
-                    name="description"
-                    id="description"
+                    name=id("description")
+                    id=id("description")
+                    value=optional(&self.form_data.description)
                     label="Description"
                     input_type="textArea"
                     on_change=handle_change(FormFieldName::Description)
@@ -216,22 +261,33 @@ impl Component for NewIngredientsForm {
                 <div class="flex mt-4 items-center space-x-2 w-full">
                     <Input
                         class="w-1/2"
-                        name="name_scientific"
-                        id="name_scientific"
+                        name=id("name_scientific")
+                        id=id("name_scientific")
+                        value=optional(&self.form_data.name_scientific)
                         label="Scientific name"
                         input_type="text"
                         on_change=handle_change(FormFieldName::NameScientific)
                     />
-                    <Input
+                    <Combobox
                         class="w-1/2"
-                        name="food_subgroup"
-                        id="food_subgroup"
+                        name=id("food_subgroup")
+                        id=id("food_subgroup")
+                        value=optional(&self.form_data.food_subgroup)
                         label="Food Subgroup eg: herbs in herbs and spices"
-                        input_type="text"
-                        on_change=handle_change(FormFieldName::FoodSubgroup)
+                        options=subgroups
+                        on_change=handle_pick(FormFieldName::FoodSubgroup)
                     />
                 </div>
-                <button onclick=&self.link.callback(|_| Msg::Submit) class=button_styles>{"Submit"}</button>
+                {self.view_taxonomy_hint()}
+                <div class="flex space-x-2">
+                    <button onclick=&self.link.callback(|_| Msg::Submit) class=button_styles>{"Submit"}</button>
+                    {match &self.props.ingredient {
+                        Some(_) => html! {
+                            <button onclick=&self.link.callback(|_| Msg::Cancel) class=button_styles>{"Cancel"}</button>
+                        },
+                        None => html! {},
+                    }}
+                </div>
             </div>
         }
     }
@@ -0,0 +1,363 @@
+use crate::api::{self, IIngredient, IngredientQuery, Page, RecipeIngredient};
+use crate::components::styles::button_styles;
+use crate::components::{Combobox, Input};
+use std::collections::HashMap;
+use whats_cooking_types::ingredient_line::{match_catalog, parse_line, ParsedLine};
+use yew::{
+    html, services::fetch::FetchTask, Callback, Component, ComponentLink, Html, InputData,
+    Properties, ShouldRender,
+};
+
+/// Typed lines are linked to a catalog ingredient right away from this
+/// confidence on, below it the match is only suggested.
+const AUTO_LINK_CONFIDENCE: f64 = 0.9;
+/// Catalog ingredients searched at once for a name, the best matches first.
+const SEARCH_LIMIT: usize = 10;
+
+/// Closest catalog ingredient to a name, with the confidence of the match.
+type Match = Option<(IIngredient, f64)>;
+
+/// Repeatable editor for the ingredient lines of a recipe, each picking an
+/// ingredient from the catalog with a quantity, unit and preparation note.
+///
+/// The catalog is searched on the server as an ingredient is typed, so it is
+/// never loaded as a whole. The lines are owned by the parent, which gets
+/// every edit through `on_change`.
+pub struct IngredientLinesEditor {
+    props: Props,
+    /// Free-text line being typed, e.g. "2 1/2 cups finely chopped onions".
+    typed_line: String,
+    /// Catalog ingredients matching what was typed in the ingredient of a
+    /// line, with the index of that line.
+    options: Option<(usize, Vec<IIngredient>)>,
+    search_task: Option<FetchTask>,
+    /// Closest catalog ingredient to the name of each line not linked to one,
+    /// searched once per name.
+    matches: HashMap<String, Match>,
+    match_tasks: HashMap<String, FetchTask>,
+    /// Typed line waiting for its catalog match before being added.
+    add_task: Option<FetchTask>,
+    link: ComponentLink<Self>,
+}
+
+#[derive(Copy, Clone)]
+pub enum LineField {
+    Quantity,
+    Unit,
+    Note,
+}
+
+pub enum Msg {
+    AddLine,
+    UpdateTypedLine(String),
+    AddTypedLine,
+    TypedLineMatched(ParsedLine, Vec<IIngredient>),
+    RemoveLine(usize),
+    /// Searches the catalog for what is typed in the ingredient of a line.
+    Search(usize, String),
+    SearchSuccess(usize, Vec<IIngredient>),
+    /// Sets the ingredient of a line, linked when it is one of the options.
+    PickIngredient(usize, String),
+    MatchFound(String, Vec<IIngredient>),
+    /// Links a line to the catalog ingredient matched to its name.
+    UseMatch(usize),
+    UpdateLine(usize, LineField, String),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub lines: Vec<RecipeIngredient>,
+    pub on_change: Callback<Vec<RecipeIngredient>>,
+}
+
+fn optional(value: String) -> Option<String> {
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+impl IngredientLinesEditor {
+    /// Searches the catalog ingredients best matching `name`, which are passed
+    /// to `found`, or none of them when the search fails.
+    fn search<F>(&self, name: &str, found: F) -> Option<FetchTask>
+    where
+        F: Fn(Vec<IIngredient>) -> Msg + 'static,
+    {
+        let query = IngredientQuery {
+            q: Some(name.to_string()),
+            limit: Some(SEARCH_LIMIT),
+            ..IngredientQuery::default()
+        };
+        let callback =
+            self.link
+                .callback(move |result: Result<Page<IIngredient>, _>| match result {
+                    Ok(page) => found(page.items),
+                    Err(error) => {
+                        log::error!("failed to search ingredients: {}", error);
+                        found(vec![])
+                    }
+                });
+        match api::search_ingredients(&query, callback) {
+            Ok(task) => Some(task),
+            Err(error) => {
+                log::error!("failed to search ingredients: {}", error);
+                None
+            }
+        }
+    }
+
+    /// Searches a catalog match for the names of the unlinked lines that
+    /// haven't been matched yet.
+    fn match_lines(&mut self) {
+        let names: Vec<String> = self
+            .props
+            .lines
+            .iter()
+            .filter(|line| line.ingredient_id.is_none())
+            .map(|line| line.name.trim().to_string())
+            .filter(|name| {
+                !name.is_empty()
+                    && !self.matches.contains_key(name)
+                    && !self.match_tasks.contains_key(name)
+            })
+            .collect();
+        for name in names {
+            let matched = name.clone();
+            let found = move |ingredients| Msg::MatchFound(matched.clone(), ingredients);
+            // store the task so it isn't canceled immediately
+            if let Some(task) = self.search(&name, found) {
+                self.match_tasks.insert(name, task);
+            }
+        }
+    }
+
+    fn view_line(&self, index: usize, line: &RecipeIngredient) -> Html {
+        let field = |field: LineField| {
+            self.link
+                .callback(move |event: InputData| Msg::UpdateLine(index, field, event.value))
+        };
+        let options = match &self.options {
+            Some((searched, ingredients)) if *searched == index => ingredients
+                .iter()
+                .map(|ingredient| ingredient.name.clone())
+                .collect(),
+            _ => vec![],
+        };
+        html! {
+            <>
+                <div class="flex items-end space-x-2 mb-2">
+                    <Combobox
+                        class="w-1/3"
+                        name=format!("line-{}-ingredient", index)
+                        id=format!("line-{}-ingredient", index)
+                        label="Ingredient"
+                        value=&line.name
+                        options=options
+                        filter=false
+                        on_input=self.link.callback(move |name| Msg::Search(index, name))
+                        on_change=self.link.callback(move |name| Msg::PickIngredient(index, name))
+                    />
+                    <Input
+                        class="w-1/6"
+                        name=format!("line-{}-quantity", index)
+                        id=format!("line-{}-quantity", index)
+                        value=line.quantity.map(|q| q.to_string()).unwrap_or_default()
+                        label="Quantity"
+                        input_type="number"
+                        on_change=field(LineField::Quantity)
+                    />
+                    <Input
+                        class="w-1/6"
+                        name=format!("line-{}-unit", index)
+                        id=format!("line-{}-unit", index)
+                        value=line.unit.clone().unwrap_or_default()
+                        label="Unit"
+                        on_change=field(LineField::Unit)
+                    />
+                    <Input
+                        class="w-1/3"
+                        name=format!("line-{}-note", index)
+                        id=format!("line-{}-note", index)
+                        value=line.note.clone().unwrap_or_default()
+                        label="Preparation eg: finely chopped"
+                        on_change=field(LineField::Note)
+                    />
+                    <button class="pb-3" title="remove" onclick=self.link.callback(move |_| Msg::RemoveLine(index))>{"❌"}</button>
+                </div>
+                {self.view_suggestion(index, line)}
+            </>
+        }
+    }
+
+    /// Offers the closest catalog ingredient for a line that names one the
+    /// catalog doesn't have.
+    fn view_suggestion(&self, index: usize, line: &RecipeIngredient) -> Html {
+        if line.ingredient_id.is_some() {
+            return html! {};
+        }
+        match self.matches.get(line.name.trim()) {
+            Some(Some((ingredient, confidence))) => html! {
+                <button
+                    class="ml-2 mb-2 text-sm text-indigo-600 hover:underline"
+                    onclick=self.link.callback(move |_| Msg::UseMatch(index))
+                >
+                    {format!("Use {} ({:.0}% match)", ingredient.name, confidence * 100.0)}
+                </button>
+            },
+            _ => html! {},
+        }
+    }
+}
+
+impl Component for IngredientLinesEditor {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut editor = Self {
+            props,
+            typed_line: String::new(),
+            options: None,
+            search_task: None,
+            matches: HashMap::new(),
+            match_tasks: HashMap::new(),
+            add_task: None,
+            link,
+        };
+        editor.match_lines();
+        editor
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut lines = self.props.lines.clone();
+        match msg {
+            Msg::AddLine => lines.push(RecipeIngredient::default()),
+            Msg::UpdateTypedLine(value) => {
+                self.typed_line = value;
+                return true;
+            }
+            Msg::AddTypedLine => {
+                let parsed = parse_line(&self.typed_line);
+                if parsed.name.is_empty() {
+                    return false;
+                }
+                self.typed_line.clear();
+                let name = parsed.name.clone();
+                let unmatched = parsed.clone();
+                let found = move |ingredients| Msg::TypedLineMatched(parsed.clone(), ingredients);
+                match self.search(&name, found) {
+                    Some(task) => self.add_task = Some(task),
+                    None => self
+                        .link
+                        .send_message(Msg::TypedLineMatched(unmatched, vec![])),
+                }
+                return true;
+            }
+            Msg::TypedLineMatched(parsed, ingredients) => {
+                self.add_task = None;
+                let linked = match_catalog(&parsed.name, &ingredients)
+                    .filter(|found| found.confidence >= AUTO_LINK_CONFIDENCE)
+                    .map(|found| found.ingredient);
+                lines.push(parsed.into_recipe_ingredient(linked));
+            }
+            Msg::RemoveLine(index) => {
+                if index < lines.len() {
+                    lines.remove(index);
+                }
+                // the options belong to a line that may have moved
+                self.options = None;
+            }
+            Msg::Search(index, name) => {
+                let found = move |ingredients| Msg::SearchSuccess(index, ingredients);
+                // replacing the task cancels the search for what was typed before
+                self.search_task = self.search(&name, found);
+                return false;
+            }
+            Msg::SearchSuccess(index, ingredients) => {
+                self.search_task = None;
+                self.options = Some((index, ingredients));
+                return true;
+            }
+            Msg::PickIngredient(index, name) => {
+                let ingredient = match &self.options {
+                    Some((searched, ingredients)) if *searched == index => {
+                        ingredients.iter().find(|i| i.name == name)
+                    }
+                    _ => None,
+                };
+                if let Some(line) = lines.get_mut(index) {
+                    // a name that isn't in the catalog is kept, unlinked
+                    line.ingredient_id = ingredient.map(|i| i.id);
+                    line.name = name;
+                }
+            }
+            Msg::MatchFound(name, ingredients) => {
+                self.match_tasks.remove(&name);
+                let found = match_catalog(&name, &ingredients)
+                    .map(|found| (found.ingredient.clone(), found.confidence));
+                self.matches.insert(name, found);
+                return true;
+            }
+            Msg::UseMatch(index) => {
+                if let Some(line) = lines.get_mut(index) {
+                    if let Some(Some((ingredient, _))) = self.matches.get(line.name.trim()) {
+                        line.ingredient_id = Some(ingredient.id);
+                        line.name = ingredient.name.clone();
+                    }
+                }
+            }
+            Msg::UpdateLine(index, field, value) => {
+                if let Some(line) = lines.get_mut(index) {
+                    match field {
+                        LineField::Quantity => line.quantity = value.trim().parse().ok(),
+                        LineField::Unit => line.unit = optional(value),
+                        LineField::Note => line.note = optional(value),
+                    }
+                }
+            }
+        }
+        self.props.on_change.emit(lines);
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        self.match_lines();
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="mt-4">
+                <h4 class="ml-2 mb-2 text-sm text-gray-600">{"Ingredients"}</h4>
+                {self.props.lines
+                    .iter()
+                    .enumerate()
+                    .map(|(index, line)| self.view_line(index, line))
+                    .collect::<Html>()}
+                <div class="flex items-end space-x-2">
+                    <Input
+                        class="w-2/3"
+                        name="typed-line"
+                        id="typed-line"
+                        value=&self.typed_line
+                        label="Type a line, eg: 2 1/2 cups finely chopped onions"
+                        on_change=self.link.callback(|event: InputData| Msg::UpdateTypedLine(event.value))
+                    />
+                    <button
+                        class=button_styles
+                        disabled=self.add_task.is_some()
+                        onclick=self.link.callback(|_| Msg::AddTypedLine)
+                    >
+                        {"Add line"}
+                    </button>
+                    <button class=button_styles onclick=self.link.callback(|_| Msg::AddLine)>
+                        {"Add ingredient"}
+                    </button>
+                </div>
+            </div>
+        }
+    }
+}
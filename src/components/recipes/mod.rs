@@ -1,3 +1,7 @@
+mod ingredient_lines;
 mod newRecipeForm;
+mod recipe_card;
 
+pub use ingredient_lines::IngredientLinesEditor;
 pub use newRecipeForm::NewRecipesForm;
+pub use recipe_card::RecipeCard;
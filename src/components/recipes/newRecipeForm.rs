@@ -1,33 +1,23 @@
+use crate::api::{self, ApiError, NewRecipe, RecipeIngredient};
+use crate::components::recipes::IngredientLinesEditor;
 use crate::components::styles::button_styles;
-use crate::components::Input;
-use crate::yew::format::Json;
+use crate::components::{Input, TextArea};
+use crate::recipe_import::parse_recipe;
 use crate::yew::InputData;
-use serde::{Deserialize, Serialize};
 use yew::{
-    html,
-    services::{
-        fetch::{FetchTask, Request, Response},
-        FetchService,
-    },
-    Children, Component, ComponentLink, Html, Properties, ShouldRender,
+    html, services::fetch::FetchTask, Callback, Children, Component, ComponentLink, Html,
+    Properties, ShouldRender,
 };
 
-#[derive(Deserialize)]
-struct PostResponse {
-    response: String,
-}
-
-#[derive(Serialize, Default, Deserialize, Clone, Debug)]
-pub struct FormData {
-    name: String,
-    description: Option<String>,
-    link: Option<String>,
-    url: Option<String>,
-}
-
 pub struct NewRecipesForm {
     link: ComponentLink<Self>,
-    form_data: FormData,
+    props: Props,
+    form_data: NewRecipe,
+    /// Raw contents of the steps text area, one step per line.
+    steps_text: String,
+    /// HTML or JSON-LD pasted to fill in the form from.
+    import_text: String,
+    import_error: Option<String>,
     state: States,
     fetch_task: Option<FetchTask>,
 }
@@ -38,6 +28,22 @@ pub enum FormFieldName {
     Description,
     Url,
     Link,
+    Yields,
+    PrepTime,
+    CookTime,
+    Steps,
+}
+
+fn parse_minutes(value: &str) -> Option<u32> {
+    value.trim().parse().ok()
+}
+
+fn split_steps(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
 }
 
 pub enum States {
@@ -45,53 +51,95 @@ pub enum States {
     Invalid,
     Submitting,
     Success,
-    Error(anyhow::Error),
+    Error(ApiError),
 }
 
 pub enum Msg {
     UpdateFormField(FormFieldName, String),
+    UpdateIngredients(Vec<RecipeIngredient>),
+    UpdateImportText(String),
+    ImportRecipe,
     Submit,
     StartSubmit,
     SubmitSuccess,
-    SubmitError(anyhow::Error),
+    SubmitError(ApiError),
 }
 
 #[derive(Properties, Clone)]
 pub struct Props {
     #[prop_or_default]
     pub children: Children,
+    /// Called after a recipe was saved, e.g. to refresh a list of recipes.
+    #[prop_or_default]
+    pub on_created: Callback<()>,
 }
 
 impl Component for NewRecipesForm {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         Self {
+            props,
             fetch_task: None,
             state: States::Initial,
-            form_data: FormData {
-                name: String::from(""),
+            form_data: NewRecipe {
                 description: Some(String::from("")),
-                link: None,
-                url: None,
+                ..NewRecipe::default()
             },
+            steps_text: String::new(),
+            import_text: String::new(),
+            import_error: None,
             link,
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
         match self.state {
-            States::Initial | States::Invalid | States::Error(_) => match msg {
+            // The form stays usable after a successful submit so several recipes can be added.
+            States::Initial | States::Invalid | States::Success | States::Error(_) => match msg {
                 Msg::UpdateFormField(field_name, input_data) => match field_name {
                     FormFieldName::Name => self.form_data.name = input_data,
                     FormFieldName::Description => self.form_data.description = Some(input_data),
                     FormFieldName::Url => self.form_data.url = Some(input_data),
                     FormFieldName::Link => self.form_data.link = Some(input_data),
+                    FormFieldName::Yields => self.form_data.yields = Some(input_data),
+                    FormFieldName::PrepTime => {
+                        self.form_data.prep_time = parse_minutes(&input_data)
+                    }
+                    FormFieldName::CookTime => {
+                        self.form_data.cook_time = parse_minutes(&input_data)
+                    }
+                    FormFieldName::Steps => self.steps_text = input_data,
+                },
+                Msg::UpdateIngredients(lines) => self.form_data.ingredients = lines,
+                Msg::UpdateImportText(text) => self.import_text = text,
+                Msg::ImportRecipe => match parse_recipe(&self.import_text) {
+                    Ok(mut recipe) => {
+                        // keep a link typed in before pasting when the recipe has none
+                        recipe.link = recipe.link.or_else(|| self.form_data.link.take());
+                        self.steps_text = recipe.steps.join("\n");
+                        self.form_data = recipe;
+                        self.import_text.clear();
+                        self.import_error = None;
+                        self.state = States::Initial;
+                    }
+                    Err(error) => self.import_error = Some(error.to_string()),
                 },
                 Msg::Submit => {
+                    self.form_data.steps = split_steps(&self.steps_text);
+                    // lines where no ingredient was picked are left out
+                    self.form_data
+                        .ingredients
+                        .retain(|line| !line.name.trim().is_empty());
                     log::info!("data: {:?}", self.form_data);
 
+                    if self.form_data.name.trim().is_empty() {
+                        self.state = States::Invalid
+                    } else {
+                        self.state = States::Submitting
+                    }
+
                     // returning same event so that it triggers next event
                     self.link.send_message(Msg::StartSubmit);
                     // validate data
@@ -105,54 +153,34 @@ impl Component for NewRecipesForm {
                 Msg::StartSubmit => {
                     // do the fetch
                     log::info!("sending post request");
-                    let request = Request::post("http://localhost:8082/recipes")
-                        .header("Content-Type", "application/json")
-                        .body(Json(&self.form_data))
-                        .expect("Could not build request.");
-                    // 2. construct a callback
-                    let callback = self.link.callback(
-                        |response: Response<Json<Result<PostResponse, anyhow::Error>>>| {
-                            let Json(data) = response.into_body();
-                            // log::info!("got data {:?}", data);
-                            match data {
-                                Ok(_) => Msg::SubmitSuccess,
-                                Err(error) => Msg::SubmitError(error),
-                            }
-                        },
-                    );
-                    // 3. pass the request and callback to the fetch service
-                    let task =
-                        FetchService::fetch(request, callback).expect("failed to start request");
-                    // 4. store the task so it isn't canceled immediately
-                    self.fetch_task = Some(task);
-                    // we want to redraw so that the page displays a 'fetching...' message to the user
-                    // so return 'true'
+                    let callback = self.link.callback(|result| match result {
+                        Ok(_) => Msg::SubmitSuccess,
+                        Err(error) => Msg::SubmitError(error),
+                    });
+                    match api::create_recipe(&self.form_data, callback) {
+                        // store the task so it isn't canceled immediately
+                        Ok(task) => self.fetch_task = Some(task),
+                        Err(error) => self.state = States::Error(error),
+                    }
+                }
+                Msg::SubmitSuccess => {
+                    self.state = States::Success;
+                    self.form_data = NewRecipe {
+                        description: Some(String::from("")),
+                        ..NewRecipe::default()
+                    };
+                    self.steps_text.clear();
+                    self.props.on_created.emit(());
                 }
-                Msg::SubmitSuccess => self.state = States::Success,
                 Msg::SubmitError(error) => self.state = States::Error(error),
                 _ => {}
             },
-            States::Success => {
-                self.form_data = FormData {
-                    name: String::from(""),
-                    description: Some(String::from("")),
-                    url: None,
-                    link: None,
-                }
-            }
-            States::Error(_) => {
-                self.form_data = FormData {
-                    name: String::from(""),
-                    description: Some(String::from("")),
-                    url: None,
-                    link: None,
-                }
-            }
         }
         true
     }
 
-    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
         false
     }
 
@@ -162,6 +190,8 @@ impl Component for NewRecipesForm {
                 .link
                 .callback(move |event: InputData| Msg::UpdateFormField(field, event.value));
         };
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let minutes = |value: Option<u32>| value.map(|m| m.to_string()).unwrap_or_default();
 
         html! {
             <div class="my-6 w-full">
@@ -173,11 +203,30 @@ impl Component for NewRecipesForm {
                     States::Error(errorMessage) => html! { <h2>{errorMessage}</h2> }
                 }}</h1>
                 <h3 class="mb-4 pl-2 uppercase tracking-wide text-sm text-indigo-600 font-bold">{"New Recipe"}</h3>
+                <TextArea
+                    name="import"
+                    value=&self.import_text
+                    rows=3
+                    label="Paste a recipe page's HTML or its schema.org JSON-LD to fill in the form"
+                    on_change=self.link.callback(|event: InputData| Msg::UpdateImportText(event.value))
+                />
+                {match &self.import_error {
+                    Some(error) => html! { <p class="ml-2 text-red-600">{error}</p> },
+                    None => html! {},
+                }}
+                <button
+                    onclick=self.link.callback(|_| Msg::ImportRecipe)
+                    class=button_styles
+                    disabled=self.import_text.trim().is_empty()
+                >
+                    {"Fill in from recipe"}
+                </button>
                 <div class="flex mb-4 items-center space-x-2 w-full mt-2">
                     <Input
                         class="w-1/2"
                         name="name"
                         id="name"
+                        value=&self.form_data.name
                         label="Recipe name"
                         input_type="text"
                         on_change=handle_change(FormFieldName::Name)
@@ -187,6 +236,7 @@ impl Component for NewRecipesForm {
                 <Input
                     name="description"
                     id="description"
+                    value=optional(&self.form_data.description)
                     label="Description"
                     input_type="textArea"
                     on_change=handle_change(FormFieldName::Description)
@@ -196,6 +246,7 @@ impl Component for NewRecipesForm {
                         class="w-1/2"
                         name="url"
                         id="url"
+                        value=optional(&self.form_data.url)
                         label="Photo"
                         input_type="text"
                         on_change=handle_change(FormFieldName::Url)
@@ -204,11 +255,52 @@ impl Component for NewRecipesForm {
                         class="w-1/2"
                         name="link"
                         id="link"
+                        value=optional(&self.form_data.link)
                         label="Link to recipe on external website (if exists)"
                         input_type="text"
                         on_change=handle_change(FormFieldName::Link)
                     />
                 </div>
+                <div class="flex mt-4 items-center space-x-2 w-full">
+                    <Input
+                        class="w-1/3"
+                        name="yields"
+                        id="yields"
+                        value=optional(&self.form_data.yields)
+                        label="Yields eg: 4 servings"
+                        input_type="text"
+                        on_change=handle_change(FormFieldName::Yields)
+                    />
+                    <Input
+                        class="w-1/3"
+                        name="prep_time"
+                        id="prep_time"
+                        value=minutes(self.form_data.prep_time)
+                        label="Prep time (minutes)"
+                        input_type="number"
+                        on_change=handle_change(FormFieldName::PrepTime)
+                    />
+                    <Input
+                        class="w-1/3"
+                        name="cook_time"
+                        id="cook_time"
+                        value=minutes(self.form_data.cook_time)
+                        label="Cook time (minutes)"
+                        input_type="number"
+                        on_change=handle_change(FormFieldName::CookTime)
+                    />
+                </div>
+                <IngredientLinesEditor
+                    lines=self.form_data.ingredients.clone()
+                    on_change=self.link.callback(Msg::UpdateIngredients)
+                />
+                <TextArea
+                    class="mt-4"
+                    name="steps"
+                    value=&self.steps_text
+                    label="Steps, one per line"
+                    on_change=handle_change(FormFieldName::Steps)
+                />
                 <button onclick=&self.link.callback(|_| Msg::Submit) class=button_styles>{"Submit"}</button>
             </div>
         }
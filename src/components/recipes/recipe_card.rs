@@ -0,0 +1,60 @@
+use crate::api::Recipe;
+use crate::routes::{AppAnchor, AppRoutes};
+use yew::{html, Component, ComponentLink, Html, Properties, ShouldRender};
+
+pub struct RecipeCard {
+    props: Props,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub recipe: Recipe,
+}
+
+impl Component for RecipeCard {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let recipe = &self.props.recipe;
+        html! {
+            <div class="md:flex my-4">
+                {match &recipe.url {
+                    Some(url) if !url.is_empty() => html! {
+                        <div class="md:flex-shrink-0">
+                            <img class="rounded-lg md:w-56 object-cover" src=url alt=&recipe.name />
+                        </div>
+                    },
+                    _ => html! {},
+                }}
+                <div class="mt-4 md:mt-0 md:ml-6">
+                    <AppAnchor route=AppRoutes::RecipeDetailPage(recipe.id)>
+                        <h3 class="block mt-1 text-lg leading-tight font-semibold text-gray-900 hover:underline">{&recipe.name}</h3>
+                    </AppAnchor>
+                    <p class="mt-2 text-gray-600">{recipe.description.as_deref().unwrap_or("")}</p>
+                    {match &recipe.link {
+                        Some(link) if !link.is_empty() => html! {
+                            <a href=link.as_str() target="_blank" rel="noopener noreferrer" class="mt-2 block text-sm text-indigo-600 hover:underline">
+                                {"View original recipe"}
+                            </a>
+                        },
+                        _ => html! {},
+                    }}
+                </div>
+            </div>
+        }
+    }
+}
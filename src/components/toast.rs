@@ -0,0 +1,85 @@
+use std::time::Duration;
+use yew::services::{timeout::TimeoutTask, TimeoutService};
+use yew::{html, Callback, Component, ComponentLink, Html, Properties, ShouldRender};
+
+/// Message shown in a corner of the page for a few seconds, with a button,
+/// e.g. to undo what was just done.
+pub struct Toast {
+    props: Props,
+    /// Dismisses the toast once it runs out, dropping it cancels that.
+    timeout_task: Option<TimeoutTask>,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    Action,
+    Dismiss,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub message: String,
+    pub action_label: String,
+    pub on_action: Callback<()>,
+    /// Called when the toast was closed or timed out.
+    pub on_dismiss: Callback<()>,
+    #[prop_or(6000)]
+    pub timeout_ms: u64,
+}
+
+impl Toast {
+    fn start_timeout(&mut self) {
+        let delay = Duration::from_millis(self.props.timeout_ms);
+        let callback = self.link.callback(|_| Msg::Dismiss);
+        self.timeout_task = Some(TimeoutService::spawn(delay, callback));
+    }
+}
+
+impl Component for Toast {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut toast = Self {
+            props,
+            timeout_task: None,
+            link,
+        };
+        toast.start_timeout();
+        toast
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        self.timeout_task = None;
+        match msg {
+            Msg::Action => self.props.on_action.emit(()),
+            Msg::Dismiss => self.props.on_dismiss.emit(()),
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props == self.props {
+            return false;
+        }
+        // another message gets the full time again
+        let restart = props.message != self.props.message;
+        self.props = props;
+        if restart {
+            self.start_timeout();
+        }
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="fixed bottom-0 right-0 m-4 flex items-center space-x-4 px-4 py-3 rounded-md shadow-lg bg-gray-800 text-white" role="status">
+                <span>{&self.props.message}</span>
+                <button class="font-bold text-yellow-300" onclick=self.link.callback(|_| Msg::Action)>
+                    {&self.props.action_label}
+                </button>
+                <button title="close" onclick=self.link.callback(|_| Msg::Dismiss)>{"✕"}</button>
+            </div>
+        }
+    }
+}
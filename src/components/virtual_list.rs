@@ -0,0 +1,191 @@
+use crate::windowing::visible_rows;
+use std::ops::Range;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Event, HtmlElement};
+use yew::{html, Callback, Component, ComponentLink, Html, NodeRef, Properties, ShouldRender};
+
+/// Renders row `index` of the list.
+pub type RenderRow = Rc<dyn Fn(usize) -> Html>;
+
+/// Scrolling list that only mounts the rows in view plus a buffer, so lists
+/// of thousands of rows stay responsive.
+///
+/// Rows are measured once mounted, the ones never mounted are assumed to be
+/// `row_height` pixels tall. Each row must render a single element with a
+/// key, so rows staying mounted keep their state while the list scrolls.
+pub struct VirtualList {
+    props: Props,
+    scroll_top: f64,
+    /// Height of every row, measured or estimated.
+    heights: Vec<f64>,
+    node_ref: NodeRef,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    Scroll(f64),
+    Measured,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub row_count: usize,
+    pub render_row: RenderRow,
+    /// Estimated height of the rows not measured yet.
+    #[prop_or(40.0)]
+    pub row_height: f64,
+    /// Height of the scrolling viewport in pixels.
+    #[prop_or(600.0)]
+    pub height: f64,
+    /// Rows mounted above and below the viewport.
+    #[prop_or(10)]
+    pub buffer: usize,
+    /// Rows kept mounted out of view too, e.g. the ones being edited, so they
+    /// keep their state.
+    #[prop_or_default]
+    pub pinned: Vec<usize>,
+    /// Called when the last row is mounted, e.g. to fetch more rows.
+    #[prop_or_default]
+    pub on_end_reached: Callback<()>,
+}
+
+impl VirtualList {
+    fn visible(&self) -> Range<usize> {
+        visible_rows(
+            self.scroll_top,
+            self.props.height,
+            &self.heights,
+            self.props.buffer,
+        )
+    }
+
+    /// Indices of the mounted rows, in order.
+    fn mounted(&self) -> Vec<usize> {
+        let visible = self.visible();
+        let mut rows: Vec<usize> = visible.clone().collect();
+        rows.extend(
+            self.props
+                .pinned
+                .iter()
+                .filter(|index| **index < self.props.row_count && !visible.contains(index)),
+        );
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+
+    /// Stores the heights of the mounted rows, returning whether any changed.
+    fn measure(&mut self) -> bool {
+        let list = match self.node_ref.cast::<Element>() {
+            Some(list) => list,
+            None => return false,
+        };
+        let mut changed = false;
+        let mut element = list.first_element_child();
+        let mut next = 0;
+        for index in self.mounted() {
+            if index > next {
+                // skip the spacer standing for the rows in between
+                element = element.and_then(|spacer| spacer.next_element_sibling());
+            }
+            let row = match element {
+                Some(row) => row,
+                None => break,
+            };
+            if let Ok(row) = row.clone().dyn_into::<HtmlElement>() {
+                let height = row.offset_height() as f64;
+                if (self.heights[index] - height).abs() >= 1.0 {
+                    self.heights[index] = height;
+                    changed = true;
+                }
+            }
+            element = row.next_element_sibling();
+            next = index + 1;
+        }
+        changed
+    }
+
+    fn spacer(&self, rows: Range<usize>) -> Html {
+        let key = format!("spacer-{}", rows.start);
+        let height: f64 = self.heights[rows].iter().sum();
+        html! { <div key=key style=format!("height: {}px", height)></div> }
+    }
+}
+
+impl Component for VirtualList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            heights: vec![props.row_height; props.row_count],
+            props,
+            scroll_top: 0.0,
+            node_ref: NodeRef::default(),
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Scroll(scroll_top) => {
+                // rows may have grown or shrunk since the last render
+                let measured = self.measure();
+                let before = self.visible();
+                self.scroll_top = scroll_top;
+                let after = self.visible();
+                if after.end == self.props.row_count && before.end < after.end {
+                    self.props.on_end_reached.emit(());
+                }
+                // only re-render when other rows come into view or sizes changed
+                measured || before != after
+            }
+            Msg::Measured => true,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.heights.resize(props.row_count, props.row_height);
+        self.props = props;
+        true
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        if self.measure() {
+            self.link.send_message(Msg::Measured);
+        }
+    }
+
+    fn view(&self) -> Html {
+        let mut rows = vec![];
+        let mut next = 0;
+        for index in self.mounted() {
+            if index > next {
+                rows.push(self.spacer(next..index));
+            }
+            rows.push((self.props.render_row)(index));
+            next = index + 1;
+        }
+        if next < self.props.row_count {
+            rows.push(self.spacer(next..self.props.row_count));
+        }
+        html! {
+            <div
+                ref=self.node_ref.clone()
+                class="overflow-y-auto"
+                style=format!("height: {}px", self.props.height)
+                onscroll=self.link.callback(|event: Event| {
+                    let scroll_top = event
+                        .target()
+                        .and_then(|target| target.dyn_into::<Element>().ok())
+                        .map(|element| element.scroll_top() as f64)
+                        .unwrap_or(0.0);
+                    Msg::Scroll(scroll_top)
+                })
+            >
+                {for rows}
+            </div>
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+
+/// Name of the `<meta>` tag in `index.html` holding the backend base URL.
+const API_BASE_URL_META: &str = "api-base-url";
+const DEFAULT_API_BASE_URL: &str = "http://localhost:8082";
+
+thread_local! {
+    static CONFIG: RefCell<Config> = RefCell::new(Config::default());
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub api_base_url: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            api_base_url: option_env!("API_BASE_URL")
+                .unwrap_or(DEFAULT_API_BASE_URL)
+                .to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the configuration from the page, falling back to the value of the
+    /// `API_BASE_URL` env var at compile time and finally to `localhost:8082`.
+    pub fn from_document() -> Self {
+        let mut config = Self::default();
+        if let Some(url) = read_meta(API_BASE_URL_META) {
+            config.api_base_url = url;
+        }
+        config.api_base_url = config.api_base_url.trim_end_matches('/').to_string();
+        config
+    }
+}
+
+fn read_meta(name: &str) -> Option<String> {
+    let document = web_sys::window()?.document()?;
+    let element = document
+        .query_selector(&format!("meta[name=\"{}\"]", name))
+        .ok()??;
+    element
+        .get_attribute("content")
+        .filter(|content| !content.trim().is_empty())
+}
+
+/// Stores the configuration used by the rest of the app. Called once from `run_app`.
+pub fn init(config: Config) {
+    log::info!("using config {:?}", config);
+    CONFIG.with(|current| *current.borrow_mut() = config);
+}
+
+pub fn api_base_url() -> String {
+    CONFIG.with(|config| config.borrow().api_base_url.clone())
+}
@@ -0,0 +1,177 @@
+//! Reading ingredients out of CSV files for the import page.
+
+use whats_cooking_types::{taxonomy::Taxonomy, NewIngredient};
+
+/// Ingredient field a CSV column can be imported into.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    Name,
+    NameScientific,
+    Description,
+    FoodGroup,
+    FoodSubgroup,
+}
+
+impl Column {
+    pub const ALL: [Column; 5] = [
+        Column::Name,
+        Column::NameScientific,
+        Column::Description,
+        Column::FoodGroup,
+        Column::FoodSubgroup,
+    ];
+
+    /// Name of the field as the api spells it.
+    pub fn field(self) -> &'static str {
+        match self {
+            Column::Name => "name",
+            Column::NameScientific => "name_scientific",
+            Column::Description => "decription",
+            Column::FoodGroup => "food_group",
+            Column::FoodSubgroup => "food_subgroup",
+        }
+    }
+
+    /// The field a header most likely holds, e.g. "Scientific name" or "food_group".
+    pub fn guess(header: &str) -> Option<Column> {
+        let header: String = header
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        match header.as_str() {
+            "name" | "ingredient" => Some(Column::Name),
+            "name_scientific" | "scientific_name" => Some(Column::NameScientific),
+            "decription" | "description" => Some(Column::Description),
+            "food_group" | "group" => Some(Column::FoodGroup),
+            "food_subgroup" | "subgroup" => Some(Column::FoodSubgroup),
+            _ => None,
+        }
+    }
+}
+
+/// Guesses the field of every header, leaving unknown headers and repeats of
+/// an already mapped field unmapped.
+pub fn guess_mapping(headers: &[String]) -> Vec<Option<Column>> {
+    let mut mapping: Vec<Option<Column>> = vec![];
+    for header in headers {
+        let column = Column::guess(header).filter(|column| !mapping.contains(&Some(*column)));
+        mapping.push(column);
+    }
+    mapping
+}
+
+/// One data row of a CSV file, as it would be imported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportRow {
+    /// 1-based position among the data rows, the header not counted.
+    pub row: usize,
+    pub ingredient: NewIngredient,
+    /// Why the row can't be imported, empty when it can.
+    pub errors: Vec<String>,
+}
+
+impl ImportRow {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+fn optional(value: &str) -> Option<String> {
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+/// Turns the data `records` into ingredients using `mapping`, the field of
+/// each column, and checks them the way the server would.
+///
+/// Group and subgroup names are spelled as in the bundled taxonomy.
+pub fn import_rows(records: &[Vec<String>], mapping: &[Option<Column>]) -> Vec<ImportRow> {
+    let taxonomy = Taxonomy::bundled();
+    records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            let mut ingredient = NewIngredient::default();
+            for (value, column) in record.iter().zip(mapping) {
+                match column {
+                    Some(Column::Name) => ingredient.name = value.trim().to_string(),
+                    Some(Column::NameScientific) => ingredient.name_scientific = optional(value),
+                    Some(Column::Description) => ingredient.description = optional(value),
+                    Some(Column::FoodGroup) => ingredient.food_group = value.trim().to_string(),
+                    Some(Column::FoodSubgroup) => ingredient.food_subgroup = optional(value),
+                    None => {}
+                }
+            }
+            let mut errors = vec![];
+            if record.len() > mapping.len() {
+                errors.push(format!(
+                    "has {} fields but the header has {}",
+                    record.len(),
+                    mapping.len()
+                ));
+            }
+            if ingredient.name.is_empty() {
+                errors.push(String::from("name is missing"));
+            }
+            if ingredient.food_group.is_empty() {
+                errors.push(String::from("food group is missing"));
+            } else {
+                taxonomy.canonicalize(&mut ingredient);
+            }
+            ImportRow {
+                row: index + 1,
+                ingredient,
+                errors,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{guess_mapping, import_rows, Column};
+
+    fn strings(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn guesses_each_field_once() {
+        let headers = strings(&["Name", "Scientific name", "Group", "food_group", "Calories"]);
+        assert_eq!(
+            guess_mapping(&headers),
+            vec![
+                Some(Column::Name),
+                Some(Column::NameScientific),
+                Some(Column::FoodGroup),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_rows_and_spells_groups_from_the_taxonomy() {
+        let mapping = vec![Some(Column::Name), Some(Column::FoodGroup), None];
+        let rows = import_rows(
+            &[
+                strings(&[" Basil ", "herbs and spices", "ignored"]),
+                strings(&["", "Fruits"]),
+                strings(&["Pear", "Fruits", "", "extra"]),
+            ],
+            &mapping,
+        );
+
+        assert!(rows[0].is_valid());
+        assert_eq!(rows[0].row, 1);
+        assert_eq!(rows[0].ingredient.name, "Basil");
+        assert_eq!(rows[0].ingredient.food_group, "Herbs and Spices");
+
+        assert_eq!(rows[1].errors, vec![String::from("name is missing")]);
+        assert_eq!(
+            rows[2].errors,
+            vec![String::from("has 4 fields but the header has 3")]
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Turning already fetched ingredients and recipes into files to download.
+
+use crate::csv_import::Column;
+use crate::grouping::UNGROUPED;
+use whats_cooking_types::csv::write_csv;
+use whats_cooking_types::{Ingredient, Recipe};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Json, Format::Csv, Format::Markdown];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Format::Json => "JSON",
+            Format::Csv => "CSV",
+            Format::Markdown => "Markdown",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Markdown => "md",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Csv => "text/csv",
+            Format::Markdown => "text/markdown",
+        }
+    }
+}
+
+fn optional(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+fn minutes(value: Option<u32>) -> String {
+    value.map(|minutes| minutes.to_string()).unwrap_or_default()
+}
+
+/// `ingredients` as `format`. The CSV columns are the ones the import page
+/// reads, so an export can be imported again.
+pub fn export_ingredients(ingredients: &[Ingredient], format: Format) -> String {
+    match format {
+        Format::Json => {
+            serde_json::to_string_pretty(ingredients).expect("ingredients serialize to JSON")
+        }
+        Format::Csv => {
+            let mut records = vec![std::iter::once("id")
+                .chain(Column::ALL.iter().map(|column| column.field()))
+                .map(String::from)
+                .collect()];
+            records.extend(ingredients.iter().map(|ingredient| {
+                vec![
+                    ingredient.id.to_string(),
+                    ingredient.name.clone(),
+                    optional(&ingredient.name_scientific),
+                    optional(&ingredient.description),
+                    optional(&ingredient.food_group),
+                    optional(&ingredient.food_subgroup),
+                ]
+            }));
+            write_csv(&records)
+        }
+        Format::Markdown => ingredients_markdown(ingredients),
+    }
+}
+
+/// A list of ingredients under a heading per food group, groups and names
+/// sorted alphabetically.
+fn ingredients_markdown(ingredients: &[Ingredient]) -> String {
+    let group = |ingredient: &Ingredient| {
+        ingredient
+            .food_group
+            .clone()
+            .filter(|group| !group.trim().is_empty())
+            .unwrap_or_else(|| String::from(UNGROUPED))
+    };
+    let mut sorted: Vec<&Ingredient> = ingredients.iter().collect();
+    sorted.sort_by_key(|ingredient| {
+        let group = group(ingredient);
+        (
+            group == UNGROUPED,
+            group.to_lowercase(),
+            ingredient.name.to_lowercase(),
+        )
+    });
+
+    let mut text = String::from("# Ingredients\n");
+    let mut current_group = None;
+    for ingredient in sorted {
+        let group = group(ingredient);
+        if current_group.as_ref() != Some(&group) {
+            text.push_str(&format!("\n## {}\n\n", group));
+            current_group = Some(group);
+        }
+        text.push_str(&format!("- **{}**", ingredient.name));
+        if let Some(scientific) = &ingredient.name_scientific {
+            text.push_str(&format!(" _{}_", scientific));
+        }
+        if let Some(subgroup) = &ingredient.food_subgroup {
+            text.push_str(&format!(" ({})", subgroup));
+        }
+        if let Some(description) = &ingredient.description {
+            text.push_str(&format!(": {}", description));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// `recipes` as `format`, the Markdown being a cookbook with a section per recipe.
+pub fn export_recipes(recipes: &[Recipe], format: Format) -> String {
+    match format {
+        Format::Json => serde_json::to_string_pretty(recipes).expect("recipes serialize to JSON"),
+        Format::Csv => {
+            let headers = [
+                "id",
+                "name",
+                "description",
+                "yields",
+                "prep_time",
+                "cook_time",
+                "link",
+                "ingredients",
+                "steps",
+            ];
+            let mut records = vec![headers.iter().map(|header| header.to_string()).collect()];
+            records.extend(recipes.iter().map(|recipe| {
+                vec![
+                    recipe.id.to_string(),
+                    recipe.name.clone(),
+                    optional(&recipe.description),
+                    optional(&recipe.yields),
+                    minutes(recipe.prep_time),
+                    minutes(recipe.cook_time),
+                    optional(&recipe.link),
+                    recipe
+                        .ingredients
+                        .iter()
+                        .map(|line| line.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    recipe.steps.join("\n"),
+                ]
+            }));
+            write_csv(&records)
+        }
+        Format::Markdown => {
+            let mut text = String::from("# Cookbook\n");
+            for recipe in recipes {
+                text.push_str(&recipe_markdown(recipe));
+            }
+            text
+        }
+    }
+}
+
+fn recipe_markdown(recipe: &Recipe) -> String {
+    let mut text = format!("\n## {}\n", recipe.name);
+    if let Some(description) = recipe.description.as_ref().filter(|d| !d.trim().is_empty()) {
+        text.push_str(&format!("\n{}\n", description));
+    }
+    let times = [
+        ("Yields", recipe.yields.clone()),
+        ("Prep", recipe.prep_time.map(|m| format!("{} min", m))),
+        ("Cook", recipe.cook_time.map(|m| format!("{} min", m))),
+        ("Total", recipe.total_time().map(|m| format!("{} min", m))),
+    ];
+    let details: Vec<String> = times
+        .iter()
+        .filter_map(|(label, value)| value.as_ref().map(|value| format!("{}: {}", label, value)))
+        .collect();
+    if !details.is_empty() {
+        text.push_str(&format!("\n{}\n", details.join(" · ")));
+    }
+    if let Some(link) = recipe.link.as_ref().filter(|link| !link.trim().is_empty()) {
+        text.push_str(&format!("\nSource: <{}>\n", link));
+    }
+    if !recipe.ingredients.is_empty() {
+        text.push_str("\n### Ingredients\n\n");
+        for line in &recipe.ingredients {
+            text.push_str(&format!("- {}\n", line));
+        }
+    }
+    if !recipe.steps.is_empty() {
+        text.push_str("\n### Steps\n\n");
+        for (index, step) in recipe.steps.iter().enumerate() {
+            text.push_str(&format!("{}. {}\n", index + 1, step));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_ingredients, export_recipes, Format};
+    use crate::csv_import::{guess_mapping, import_rows};
+    use whats_cooking_types::csv::parse_csv;
+    use whats_cooking_types::{Ingredient, Recipe, RecipeIngredient};
+
+    fn ingredient(id: i32, name: &str, group: Option<&str>) -> Ingredient {
+        Ingredient {
+            id,
+            name: String::from(name),
+            name_scientific: None,
+            description: None,
+            food_group: group.map(String::from),
+            food_subgroup: None,
+            foodb_id: None,
+        }
+    }
+
+    fn pancakes() -> Recipe {
+        Recipe {
+            id: 1,
+            name: String::from("Pancakes"),
+            description: Some(String::from("Fluffy, for breakfast")),
+            link: None,
+            url: None,
+            ingredients: vec![RecipeIngredient {
+                ingredient_id: Some(3),
+                name: String::from("flour"),
+                quantity: Some(2.0),
+                unit: Some(String::from("cup")),
+                note: Some(String::from("sifted")),
+            }],
+            steps: vec![String::from("Mix"), String::from("Fry")],
+            yields: Some(String::from("4 servings")),
+            prep_time: Some(10),
+            cook_time: None,
+        }
+    }
+
+    #[test]
+    fn exported_csv_can_be_imported_again() {
+        let mut basil = ingredient(1, "Basil", Some("Herbs and Spices"));
+        basil.description = Some(String::from("Sweet, \"Genovese\""));
+        let text = export_ingredients(&[basil.clone()], Format::Csv);
+
+        let mut records = parse_csv(&text);
+        let headers = records.remove(0);
+        let rows = import_rows(&records, &guess_mapping(&headers));
+        assert!(rows[0].is_valid());
+        assert_eq!(rows[0].ingredient.name, basil.name);
+        assert_eq!(rows[0].ingredient.description, basil.description);
+    }
+
+    #[test]
+    fn exported_json_matches_the_api() {
+        let ingredients = vec![ingredient(1, "Basil", None)];
+        let text = export_ingredients(&ingredients, Format::Json);
+        assert_eq!(
+            serde_json::from_str::<Vec<Ingredient>>(&text).unwrap(),
+            ingredients
+        );
+        let recipes = vec![pancakes()];
+        let text = export_recipes(&recipes, Format::Json);
+        assert_eq!(serde_json::from_str::<Vec<Recipe>>(&text).unwrap(), recipes);
+    }
+
+    #[test]
+    fn markdown_lists_ingredients_by_group() {
+        let text = export_ingredients(
+            &[
+                ingredient(1, "Salt", None),
+                ingredient(2, "pear", Some("Fruits")),
+                ingredient(3, "Apple", Some("Fruits")),
+            ],
+            Format::Markdown,
+        );
+        assert_eq!(
+            text,
+            "# Ingredients\n\n## Fruits\n\n- **Apple**\n- **pear**\n\n## Other\n\n- **Salt**\n"
+        );
+    }
+
+    #[test]
+    fn markdown_cookbook_has_a_section_per_recipe() {
+        let text = export_recipes(&[pancakes()], Format::Markdown);
+        assert_eq!(
+            text,
+            "# Cookbook\n\n## Pancakes\n\nFluffy, for breakfast\n\n\
+             Yields: 4 servings · Prep: 10 min · Total: 10 min\n\n\
+             ### Ingredients\n\n- 2 cup flour, sifted\n\n\
+             ### Steps\n\n1. Mix\n2. Fry\n"
+        );
+        let csv = parse_csv(&export_recipes(&[pancakes()], Format::Csv));
+        assert_eq!(csv[1][7], "2 cup flour, sifted");
+        assert_eq!(csv[1][8], "Mix\nFry");
+    }
+}
@@ -0,0 +1,257 @@
+//! Grouping the ingredients list into food group and subgroup sections.
+use crate::api::{IIngredient, SectionCount};
+use std::collections::{BTreeMap, HashSet};
+
+/// Section name used for ingredients without a group or subgroup.
+pub const UNGROUPED: &str = "Other";
+
+/// Identifies a food group section, or a subgroup section within one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SectionKey {
+    pub group: String,
+    pub subgroup: Option<String>,
+}
+
+/// A row of the grouped list, in display order.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Row {
+    Header {
+        key: SectionKey,
+        /// Number of ingredients in the section, fetched or not.
+        count: usize,
+        collapsed: bool,
+    },
+    /// Index of the ingredient in the list that was grouped.
+    Ingredient(usize),
+}
+
+fn section_name(value: &Option<String>) -> String {
+    match value.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => String::from(UNGROUPED),
+    }
+}
+
+/// Sorts sections by name, keeping the ungrouped section last.
+type SortKey = (bool, String);
+
+/// A subgroup section, with its ingredient count and the indexes of the
+/// ingredients fetched so far.
+#[derive(Default)]
+struct Subgroup {
+    name: String,
+    count: Option<usize>,
+    indexes: Vec<usize>,
+}
+
+impl Subgroup {
+    /// The count from the server, the fetched ingredients when there is none.
+    fn count(&self) -> usize {
+        self.count.unwrap_or(self.indexes.len())
+    }
+}
+
+/// Subgroups of a food group, by name.
+type Subgroups = BTreeMap<SortKey, Subgroup>;
+
+fn sort_key(name: &str) -> SortKey {
+    (name == UNGROUPED, name.to_lowercase())
+}
+
+fn subgroup<'a>(
+    groups: &'a mut BTreeMap<SortKey, (String, Subgroups)>,
+    group: &Option<String>,
+    subgroup: &Option<String>,
+) -> &'a mut Subgroup {
+    let group = section_name(group);
+    let name = section_name(subgroup);
+    groups
+        .entry(sort_key(&group))
+        .or_insert_with(|| (group, BTreeMap::new()))
+        .1
+        .entry(sort_key(&name))
+        .or_insert_with(|| Subgroup {
+            name,
+            ..Subgroup::default()
+        })
+}
+
+/// Groups `ingredients` by food group, then subgroup, and flattens the
+/// sections into rows, leaving out what is inside `collapsed` sections.
+///
+/// `sections` counts every ingredient matching the list, so sections show
+/// their full size and appear before any of their ingredients is fetched.
+/// Without them the fetched ingredients are counted. Ingredients keep their
+/// relative order within a section.
+pub fn grouped_rows(
+    ingredients: &[&IIngredient],
+    sections: &[SectionCount],
+    collapsed: &HashSet<SectionKey>,
+) -> Vec<Row> {
+    let mut groups: BTreeMap<SortKey, (String, Subgroups)> = BTreeMap::new();
+    for section in sections {
+        let subgroup = subgroup(&mut groups, &section.food_group, &section.food_subgroup);
+        // several spellings of a missing name end up in the same section
+        subgroup.count = Some(subgroup.count.unwrap_or(0) + section.count);
+    }
+    for (index, ingredient) in ingredients.iter().enumerate() {
+        subgroup(
+            &mut groups,
+            &ingredient.food_group,
+            &ingredient.food_subgroup,
+        )
+        .indexes
+        .push(index);
+    }
+
+    let mut rows = vec![];
+    for (group, subgroups) in groups.into_values() {
+        let key = SectionKey {
+            group: group.clone(),
+            subgroup: None,
+        };
+        let is_collapsed = collapsed.contains(&key);
+        rows.push(Row::Header {
+            key,
+            count: subgroups.values().map(Subgroup::count).sum(),
+            collapsed: is_collapsed,
+        });
+        if is_collapsed {
+            continue;
+        }
+        for subgroup in subgroups.into_values() {
+            let count = subgroup.count();
+            let key = SectionKey {
+                group: group.clone(),
+                subgroup: Some(subgroup.name),
+            };
+            let is_collapsed = collapsed.contains(&key);
+            rows.push(Row::Header {
+                key,
+                count,
+                collapsed: is_collapsed,
+            });
+            if !is_collapsed {
+                rows.extend(subgroup.indexes.into_iter().map(Row::Ingredient));
+            }
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grouped_rows, Row, SectionKey};
+    use std::collections::HashSet;
+    use whats_cooking_types::{Ingredient, SectionCount};
+
+    fn ingredient(id: i32, name: &str, group: Option<&str>, subgroup: Option<&str>) -> Ingredient {
+        Ingredient {
+            id,
+            name: String::from(name),
+            name_scientific: None,
+            description: None,
+            food_group: group.map(String::from),
+            food_subgroup: subgroup.map(String::from),
+            foodb_id: None,
+        }
+    }
+
+    fn header(group: &str, subgroup: Option<&str>, count: usize, collapsed: bool) -> Row {
+        Row::Header {
+            key: SectionKey {
+                group: String::from(group),
+                subgroup: subgroup.map(String::from),
+            },
+            count,
+            collapsed,
+        }
+    }
+
+    fn catalog() -> Vec<Ingredient> {
+        vec![
+            ingredient(1, "Tomato", Some("Vegetables"), Some("Fruit vegetables")),
+            ingredient(2, "Basil", Some("Herbs and Spices"), Some("Herbs")),
+            ingredient(3, "Mystery", None, None),
+            ingredient(4, "Cucumber", Some("Vegetables"), Some("Fruit vegetables")),
+            ingredient(5, "Potato", Some("Vegetables"), Some("Root vegetables")),
+        ]
+    }
+
+    #[test]
+    fn groups_by_food_group_then_subgroup() {
+        let catalog = catalog();
+        let ingredients: Vec<&Ingredient> = catalog.iter().collect();
+        assert_eq!(
+            grouped_rows(&ingredients, &[], &HashSet::new()),
+            vec![
+                header("Herbs and Spices", None, 1, false),
+                header("Herbs and Spices", Some("Herbs"), 1, false),
+                Row::Ingredient(1),
+                header("Vegetables", None, 3, false),
+                header("Vegetables", Some("Fruit vegetables"), 2, false),
+                Row::Ingredient(0),
+                Row::Ingredient(3),
+                header("Vegetables", Some("Root vegetables"), 1, false),
+                Row::Ingredient(4),
+                header("Other", None, 1, false),
+                header("Other", Some("Other"), 1, false),
+                Row::Ingredient(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapsed_sections_hide_their_rows() {
+        let catalog = catalog();
+        let ingredients: Vec<&Ingredient> = catalog.iter().collect();
+        let mut collapsed = HashSet::new();
+        collapsed.insert(SectionKey {
+            group: String::from("Herbs and Spices"),
+            subgroup: None,
+        });
+        collapsed.insert(SectionKey {
+            group: String::from("Vegetables"),
+            subgroup: Some(String::from("Fruit vegetables")),
+        });
+        let rows = grouped_rows(&ingredients, &[], &collapsed);
+        assert_eq!(rows[0], header("Herbs and Spices", None, 1, true));
+        assert_eq!(rows[1], header("Vegetables", None, 3, false));
+        assert_eq!(
+            rows[2],
+            header("Vegetables", Some("Fruit vegetables"), 2, true)
+        );
+        assert_eq!(
+            rows[3],
+            header("Vegetables", Some("Root vegetables"), 1, false)
+        );
+        assert_eq!(rows[4], Row::Ingredient(4));
+    }
+
+    #[test]
+    fn counts_sections_from_the_server() {
+        let catalog = catalog();
+        let ingredients: Vec<&Ingredient> = catalog[..1].iter().collect();
+        let section = |group: &str, subgroup: Option<&str>, count| SectionCount {
+            food_group: Some(String::from(group)),
+            food_subgroup: subgroup.map(String::from),
+            count,
+        };
+        let sections = vec![
+            section("Vegetables", Some("Fruit vegetables"), 12),
+            section("Vegetables", Some("Root vegetables"), 300),
+            section("Vegetables", None, 2),
+            section("Vegetables", Some(" "), 1),
+        ];
+        assert_eq!(
+            grouped_rows(&ingredients, &sections, &HashSet::new()),
+            vec![
+                header("Vegetables", None, 315, false),
+                header("Vegetables", Some("Fruit vegetables"), 12, false),
+                Row::Ingredient(0),
+                header("Vegetables", Some("Root vegetables"), 300, false),
+                header("Vegetables", Some("Other"), 3, false),
+            ]
+        );
+    }
+}
@@ -10,14 +10,24 @@ use wasm_bindgen::prelude::*;
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 use yew;
+mod api;
 mod app;
 mod components;
+mod config;
+mod csv_import;
+mod export;
+mod grouping;
+mod pantry;
+mod recipe_import;
 mod router;
 mod routes;
+mod suggestions;
+mod windowing;
 
 #[wasm_bindgen(start)]
 pub fn run_app() -> Result<(), JsValue> {
     wasm_logger::init(wasm_logger::Config::default());
+    config::init(config::Config::from_document());
     yew::start_app::<app::App>();
     Ok(())
 }
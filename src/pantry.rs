@@ -0,0 +1,142 @@
+//! Matching recipes against the ingredients a user has on hand.
+use crate::api::{IIngredient, Recipe, RecipeIngredient};
+use std::collections::HashSet;
+
+/// How well the pantry covers the ingredients of one recipe.
+#[derive(Debug)]
+pub struct RecipeMatch<'a> {
+    pub recipe: &'a Recipe,
+    /// Number of the recipe's ingredient lines the pantry covers.
+    pub covered: usize,
+    /// Ingredient lines the pantry does not cover.
+    pub missing: Vec<&'a RecipeIngredient>,
+}
+
+impl<'a> RecipeMatch<'a> {
+    pub fn required(&self) -> usize {
+        self.recipe.ingredients.len()
+    }
+
+    /// Share of the required ingredients that are covered, between 0 and 1.
+    pub fn coverage(&self) -> f64 {
+        self.covered as f64 / self.required() as f64
+    }
+}
+
+/// Ranks `recipes` by how much of each one `pantry` covers, best first.
+///
+/// Lines linked to the catalog match on ingredient id, other lines match on a
+/// case-insensitive name. Recipes without ingredients, or without a single
+/// covered ingredient, are left out.
+pub fn rank_recipes<'a>(recipes: &'a [Recipe], pantry: &[IIngredient]) -> Vec<RecipeMatch<'a>> {
+    let ids: HashSet<i32> = pantry.iter().map(|i| i.id).collect();
+    let names: HashSet<String> = pantry.iter().map(|i| i.name.to_lowercase()).collect();
+    let is_covered = |line: &RecipeIngredient| match line.ingredient_id {
+        Some(id) => ids.contains(&id),
+        None => names.contains(&line.name.to_lowercase()),
+    };
+
+    let mut matches: Vec<RecipeMatch> = recipes
+        .iter()
+        .map(|recipe| {
+            let missing: Vec<&RecipeIngredient> = recipe
+                .ingredients
+                .iter()
+                .filter(|line| !is_covered(line))
+                .collect();
+            RecipeMatch {
+                recipe,
+                covered: recipe.ingredients.len() - missing.len(),
+                missing,
+            }
+        })
+        .filter(|m| m.covered > 0)
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.coverage()
+            .partial_cmp(&a.coverage())
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.covered.cmp(&a.covered))
+            .then(a.recipe.name.cmp(&b.recipe.name))
+    });
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rank_recipes;
+    use whats_cooking_types::{Ingredient, Recipe, RecipeIngredient};
+
+    fn ingredient(id: i32, name: &str) -> Ingredient {
+        Ingredient {
+            id,
+            name: String::from(name),
+            name_scientific: None,
+            description: None,
+            food_group: None,
+            food_subgroup: None,
+            foodb_id: None,
+        }
+    }
+
+    fn line(ingredient_id: Option<i32>, name: &str) -> RecipeIngredient {
+        RecipeIngredient {
+            ingredient_id,
+            name: String::from(name),
+            ..RecipeIngredient::default()
+        }
+    }
+
+    fn recipe(id: i32, name: &str, ingredients: Vec<RecipeIngredient>) -> Recipe {
+        Recipe {
+            id,
+            name: String::from(name),
+            description: None,
+            link: None,
+            url: None,
+            ingredients,
+            steps: vec![],
+            yields: None,
+            prep_time: None,
+            cook_time: None,
+        }
+    }
+
+    #[test]
+    fn ranks_recipes_by_coverage_and_lists_missing_ingredients() {
+        let recipes = vec![
+            recipe(
+                1,
+                "Pesto",
+                vec![
+                    line(Some(1), "Basil"),
+                    line(Some(2), "Garlic"),
+                    line(None, "Pine nuts"),
+                ],
+            ),
+            recipe(
+                2,
+                "Garlic bread",
+                vec![line(Some(2), "Garlic"), line(Some(3), "Bread")],
+            ),
+            recipe(3, "Toast", vec![line(Some(3), "Bread")]),
+            recipe(4, "Water", vec![]),
+        ];
+        let pantry = vec![
+            ingredient(1, "Basil"),
+            ingredient(2, "Garlic"),
+            ingredient(9, "pine NUTS"),
+        ];
+
+        let matches = rank_recipes(&recipes, &pantry);
+
+        let names: Vec<&str> = matches.iter().map(|m| m.recipe.name.as_str()).collect();
+        assert_eq!(names, vec!["Pesto", "Garlic bread"]);
+        assert_eq!(matches[0].covered, 3);
+        assert!(matches[0].missing.is_empty());
+        assert_eq!(matches[1].covered, 1);
+        assert_eq!(matches[1].required(), 2);
+        assert_eq!(matches[1].missing[0].name, "Bread");
+    }
+}
@@ -0,0 +1,367 @@
+//! Reading recipes out of schema.org `Recipe` JSON-LD, which most recipe
+//! websites embed in their pages, see <https://schema.org/Recipe>.
+
+use serde_json::Value;
+use std::fmt;
+use whats_cooking_types::ingredient_line::parse_line;
+use whats_cooking_types::NewRecipe;
+
+#[derive(Debug, PartialEq)]
+pub enum RecipeImportError {
+    /// The text is neither JSON nor HTML with a JSON-LD script.
+    NoJsonLd,
+    /// A JSON-LD block could not be parsed.
+    Json(String),
+    /// The JSON-LD describes something other than a recipe.
+    NoRecipe,
+}
+
+impl fmt::Display for RecipeImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecipeImportError::NoJsonLd => write!(f, "No JSON-LD found, paste the page's HTML"),
+            RecipeImportError::Json(error) => write!(f, "Invalid JSON-LD: {}", error),
+            RecipeImportError::NoRecipe => write!(f, "The JSON-LD does not describe a recipe"),
+        }
+    }
+}
+
+impl std::error::Error for RecipeImportError {}
+
+/// Reads the first schema.org `Recipe` in `text`, either JSON-LD or the
+/// HTML of a page embedding it in `<script type="application/ld+json">`.
+///
+/// Ingredient lines are split into quantity, unit, name and note but not
+/// linked to the catalog.
+pub fn parse_recipe(text: &str) -> Result<NewRecipe, RecipeImportError> {
+    let trimmed = text.trim();
+    let blocks = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        vec![trimmed]
+    } else {
+        json_ld_blocks(text)
+    };
+    if blocks.is_empty() {
+        return Err(RecipeImportError::NoJsonLd);
+    }
+    let mut error = None;
+    for block in blocks {
+        match serde_json::from_str::<Value>(block) {
+            Ok(value) => {
+                if let Some(recipe) = find_recipe(&value) {
+                    return Ok(new_recipe(recipe));
+                }
+            }
+            Err(json_error) => error = Some(RecipeImportError::Json(json_error.to_string())),
+        }
+    }
+    Err(error.unwrap_or(RecipeImportError::NoRecipe))
+}
+
+/// Contents of the JSON-LD scripts of `html`, in page order.
+fn json_ld_blocks(html: &str) -> Vec<&str> {
+    // lowercasing ascii keeps byte offsets, so they index `html` too
+    let lower = html.to_ascii_lowercase();
+    let mut blocks = vec![];
+    let mut position = 0;
+    while let Some(start) = lower[position..].find("<script").map(|i| i + position) {
+        let content = match lower[start..].find('>') {
+            Some(end) => start + end + 1,
+            None => break,
+        };
+        let end = lower[content..]
+            .find("</script")
+            .map_or(lower.len(), |end| content + end);
+        if lower[start..content].contains("application/ld+json") {
+            blocks.push(html[content..end].trim());
+        }
+        position = end;
+    }
+    blocks
+}
+
+fn is_recipe(value: &Value) -> bool {
+    let is_recipe_type = |value: &Value| {
+        value
+            .as_str()
+            .is_some_and(|t| t == "Recipe" || t.ends_with("/Recipe"))
+    };
+    match &value["@type"] {
+        Value::Array(types) => types.iter().any(is_recipe_type),
+        other => is_recipe_type(other),
+    }
+}
+
+/// The first recipe in `value`, looking into lists and `@graph`s.
+fn find_recipe(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(find_recipe),
+        Value::Object(_) if is_recipe(value) => Some(value),
+        Value::Object(object) => ["@graph", "mainEntity"]
+            .iter()
+            .filter_map(|key| object.get(*key))
+            .find_map(find_recipe),
+        _ => None,
+    }
+}
+
+/// Decodes the HTML entities sites commonly leave in their JSON-LD.
+fn decode_entities(text: &str) -> String {
+    [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&#039;", "'"),
+        ("&apos;", "'"),
+        ("&nbsp;", " "),
+        ("&amp;", "&"),
+    ]
+    .iter()
+    .fold(text.to_string(), |text, (entity, decoded)| {
+        text.replace(entity, decoded)
+    })
+}
+
+/// A single text out of a value that may be a string, a number or a list of them.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(decode_entities(text.trim())).filter(|text| !text.is_empty()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Array(items) => items.iter().find_map(text),
+        _ => None,
+    }
+}
+
+/// Url of an image given as a url, an `ImageObject` or a list of them.
+fn image(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(items) => items.iter().find_map(image),
+        Value::Object(_) => text(&value["url"]),
+        other => text(other),
+    }
+}
+
+/// Steps given as text, `HowToStep`s, `HowToSection`s or lists of them.
+fn instructions(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(text) => text
+            .lines()
+            .map(|line| decode_entities(line.trim()))
+            .filter(|line| !line.is_empty())
+            .collect(),
+        Value::Array(items) => items.iter().flat_map(instructions).collect(),
+        Value::Object(object) => match object.get("itemListElement") {
+            Some(steps) => instructions(steps),
+            None => text(&value["text"])
+                .or_else(|| text(&value["name"]))
+                .into_iter()
+                .collect(),
+        },
+        _ => vec![],
+    }
+}
+
+/// What the recipe makes. Sites often list a bare number next to a
+/// description, e.g. `["4", "4 servings"]`, the description wins.
+fn yields(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(items) => {
+            let texts: Vec<String> = items.iter().filter_map(text).collect();
+            texts
+                .iter()
+                .find(|text| text.chars().any(char::is_alphabetic))
+                .or_else(|| texts.first())
+                .cloned()
+        }
+        other => text(other),
+    }
+}
+
+/// Minutes in an ISO 8601 duration such as `PT1H30M` or `P0DT45M`.
+pub fn parse_duration(duration: &str) -> Option<u32> {
+    let duration = duration.trim().to_ascii_uppercase();
+    let chars = duration.strip_prefix('P')?.chars();
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut any = false;
+    for c in chars {
+        let unit = match c {
+            '0'..='9' | '.' => {
+                number.push(c);
+                continue;
+            }
+            'T' => {
+                in_time = true;
+                continue;
+            }
+            'D' => 24.0 * 60.0,
+            'H' if in_time => 60.0,
+            'M' if in_time => 1.0,
+            'S' if in_time => 1.0 / 60.0,
+            // years, months and weeks make no sense for a recipe
+            _ => return None,
+        };
+        minutes += number.parse::<f64>().ok()? * unit;
+        number.clear();
+        any = true;
+    }
+    if !any || !number.is_empty() {
+        return None;
+    }
+    Some(minutes.round() as u32)
+}
+
+fn new_recipe(recipe: &Value) -> NewRecipe {
+    let duration = |key: &str| text(&recipe[key]).and_then(|value| parse_duration(&value));
+    let prep_time = duration("prepTime");
+    // Some sites only give the total time, the rest of it is counted as cooking.
+    let cook_time = duration("cookTime").or_else(|| {
+        duration("totalTime").map(|total| total.saturating_sub(prep_time.unwrap_or(0)))
+    });
+    // `ingredients` is the older name of `recipeIngredient`
+    let lines: Vec<String> = match recipe
+        .get("recipeIngredient")
+        .unwrap_or(&recipe["ingredients"])
+    {
+        Value::Array(lines) => lines.iter().filter_map(text).collect(),
+        other => text(other).into_iter().collect(),
+    };
+    NewRecipe {
+        name: text(&recipe["name"]).unwrap_or_default(),
+        description: text(&recipe["description"]),
+        link: text(&recipe["url"]),
+        url: image(&recipe["image"]),
+        ingredients: lines
+            .iter()
+            .map(|line| parse_line(line).into_recipe_ingredient(None))
+            .collect(),
+        steps: instructions(&recipe["recipeInstructions"]),
+        yields: yields(&recipe["recipeYield"]),
+        prep_time,
+        cook_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_duration, parse_recipe, RecipeImportError};
+    use whats_cooking_types::RecipeIngredient;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <script type="application/ld+json">{"@context":"https://schema.org","@type":"WebSite","name":"Example"}</script>
+  <SCRIPT type='application/ld+json'>
+  {
+    "@context": "https://schema.org",
+    "@graph": [
+      { "@type": "Organization", "name": "Example" },
+      {
+        "@type": ["Recipe", "NewsArticle"],
+        "name": "Mac &amp; cheese",
+        "description": "Creamy and quick",
+        "url": "https://example.com/mac-and-cheese",
+        "image": [{ "@type": "ImageObject", "url": "https://example.com/mac.jpg" }],
+        "recipeYield": ["4", "4 servings"],
+        "prepTime": "PT10M",
+        "totalTime": "PT1H",
+        "recipeIngredient": ["200 g macaroni", "1 cup cheddar, grated"],
+        "recipeInstructions": [
+          {
+            "@type": "HowToSection",
+            "name": "Pasta",
+            "itemListElement": [{ "@type": "HowToStep", "text": "Boil the macaroni." }]
+          },
+          { "@type": "HowToStep", "text": "Stir in the cheese." }
+        ]
+      }
+    ]
+  }
+  </SCRIPT>
+</head>
+</html>"#;
+
+    #[test]
+    fn reads_recipes_embedded_in_pages() {
+        let recipe = parse_recipe(PAGE).unwrap();
+        assert_eq!(recipe.name, "Mac & cheese");
+        assert_eq!(recipe.description.as_deref(), Some("Creamy and quick"));
+        assert_eq!(
+            recipe.link.as_deref(),
+            Some("https://example.com/mac-and-cheese")
+        );
+        assert_eq!(recipe.url.as_deref(), Some("https://example.com/mac.jpg"));
+        assert_eq!(recipe.yields.as_deref(), Some("4 servings"));
+        assert_eq!(recipe.prep_time, Some(10));
+        // only the total time is given, the rest of it is cooking
+        assert_eq!(recipe.cook_time, Some(50));
+        assert_eq!(
+            recipe.ingredients,
+            vec![
+                RecipeIngredient {
+                    ingredient_id: None,
+                    name: String::from("macaroni"),
+                    quantity: Some(200.0),
+                    unit: Some(String::from("g")),
+                    note: None,
+                },
+                RecipeIngredient {
+                    ingredient_id: None,
+                    name: String::from("cheddar"),
+                    quantity: Some(1.0),
+                    unit: Some(String::from("cup")),
+                    note: Some(String::from("grated")),
+                },
+            ]
+        );
+        assert_eq!(
+            recipe.steps,
+            vec![
+                String::from("Boil the macaroni."),
+                String::from("Stir in the cheese.")
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_plain_json_ld() {
+        let recipe = parse_recipe(
+            r#"{"@type": "Recipe", "name": "Toast", "image": "toast.jpg", "recipeYield": 2,
+                "cookTime": "PT3M", "recipeInstructions": "Toast the bread.\nButter it."}"#,
+        )
+        .unwrap();
+        assert_eq!(recipe.name, "Toast");
+        assert_eq!(recipe.url.as_deref(), Some("toast.jpg"));
+        assert_eq!(recipe.yields.as_deref(), Some("2"));
+        assert_eq!((recipe.prep_time, recipe.cook_time), (None, Some(3)));
+        assert_eq!(recipe.steps.len(), 2);
+    }
+
+    #[test]
+    fn explains_what_is_missing() {
+        assert_eq!(
+            parse_recipe("<html><body>Hi</body></html>"),
+            Err(RecipeImportError::NoJsonLd)
+        );
+        assert_eq!(
+            parse_recipe(r#"{"@type": "Person", "name": "Ada"}"#),
+            Err(RecipeImportError::NoRecipe)
+        );
+        assert!(matches!(
+            parse_recipe("{ not json"),
+            Err(RecipeImportError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn parses_iso_durations() {
+        assert_eq!(parse_duration("PT1H30M"), Some(90));
+        assert_eq!(parse_duration("pt90m"), Some(90));
+        assert_eq!(parse_duration("P1DT2H"), Some(24 * 60 + 120));
+        assert_eq!(parse_duration("PT45S"), Some(1));
+        assert_eq!(parse_duration("P1M"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("90 minutes"), None);
+    }
+}
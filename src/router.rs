@@ -1,4 +1,7 @@
-use crate::routes::{AppRoutes, FetchServiceExample, Home, IngredientsPage, RecipesPage};
+use crate::routes::{
+    AppRoutes, DeletedIngredientsPage, FetchServiceExample, Home, ImportIngredientsPage,
+    IngredientDetailPage, IngredientsPage, PantryPage, RecipeDetailPage, RecipesPage,
+};
 use yew::{html, Component, ComponentLink, Html, ShouldRender};
 use yew_router::switch::Permissive;
 use yew_router::{route::Route, router::Router as YewRouter};
@@ -29,7 +32,13 @@ impl Component for Router {
                         AppRoutes::Home => html!{<Home />},
                         AppRoutes::FetchServiceExample => html!{<FetchServiceExample />},
                         AppRoutes::RecipesPage => html!{<RecipesPage />},
+                        AppRoutes::RecipeDetailPage(id) => html!{<RecipeDetailPage id=id />},
                         AppRoutes::IngredientsPage => html!{<IngredientsPage />},
+                        AppRoutes::SortedIngredientsPage(sort) => html!{<IngredientsPage sort=Some(sort) />},
+                        AppRoutes::DeletedIngredientsPage => html!{<DeletedIngredientsPage />},
+                        AppRoutes::ImportIngredientsPage => html!{<ImportIngredientsPage />},
+                        AppRoutes::IngredientDetailPage(id) => html!{<IngredientDetailPage id=id />},
+                        AppRoutes::PantryPage => html!{<PantryPage />},
                         AppRoutes::NotFound(Permissive(None)) => html!{"Page not found"},
                         AppRoutes::NotFound(Permissive(Some(missed_route))) => html!{format!("Page '{}' not found", missed_route)}
                     }
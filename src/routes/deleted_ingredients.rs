@@ -0,0 +1,173 @@
+use crate::api::{self, ApiError, BatchAction, BatchOutcome, BatchRequest, DeletedIngredient};
+use crate::components::button_styles;
+use crate::routes::{AppAnchor, AppRoutes};
+use wasm_bindgen::JsValue;
+use yew::{html, services::fetch::FetchTask, Component, ComponentLink, Html, ShouldRender};
+
+enum States {
+    Initial,
+    Fetching,
+    Success { deleted: Vec<DeletedIngredient> },
+    Error(String),
+}
+
+/// Ingredients deleted recently, which can be brought back from here.
+pub struct DeletedIngredientsPage {
+    state: States,
+    /// Outcome of the last restore that failed.
+    restore_error: Option<String>,
+    fetch_task: Option<FetchTask>,
+    restore_task: Option<FetchTask>,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    Fetch,
+    FetchSuccess(Vec<DeletedIngredient>),
+    FetchError(ApiError),
+    Restore(i32),
+    RestoreDone(Result<Vec<BatchOutcome>, ApiError>),
+}
+
+/// Formats seconds since the unix epoch in the user's locale.
+fn format_time(seconds: u64) -> String {
+    let date = js_sys::Date::new(&JsValue::from_f64(seconds as f64 * 1000.0));
+    String::from(date.to_locale_string("default", &JsValue::UNDEFINED))
+}
+
+impl DeletedIngredientsPage {
+    fn view_deleted(&self, deleted: &DeletedIngredient) -> Html {
+        let id = deleted.ingredient.id;
+        html! {
+            <li class="flex justify-between items-center p-2">
+                <div>
+                    <p>{&deleted.ingredient.name}</p>
+                    <p class="text-sm text-gray-600">
+                        {deleted.ingredient.food_group.as_deref().unwrap_or("")}
+                        {" · deleted "}{format_time(deleted.deleted_at)}
+                    </p>
+                </div>
+                <button
+                    class=button_styles
+                    disabled=self.restore_task.is_some()
+                    onclick=self.link.callback(move |_| Msg::Restore(id))
+                >
+                    {"Restore"}
+                </button>
+            </li>
+        }
+    }
+}
+
+impl Component for DeletedIngredientsPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            state: States::Initial,
+            restore_error: None,
+            fetch_task: None,
+            restore_task: None,
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Fetch => {
+                let callback = self.link.callback(|result| match result {
+                    Ok(deleted) => Msg::FetchSuccess(deleted),
+                    Err(error) => Msg::FetchError(error),
+                });
+                match api::list_deleted_ingredients(callback) {
+                    Ok(task) => {
+                        // store the task so it isn't canceled immediately
+                        self.fetch_task = Some(task);
+                        // keep showing the current list while it is refreshed after a restore
+                        if let States::Success { .. } = self.state {
+                            return false;
+                        }
+                        self.state = States::Fetching;
+                    }
+                    Err(error) => self.state = States::Error(error.to_string()),
+                }
+            }
+            Msg::FetchSuccess(deleted) => {
+                self.state = States::Success { deleted };
+                self.fetch_task = None;
+            }
+            Msg::FetchError(error) => {
+                self.state = States::Error(error.to_string());
+                self.fetch_task = None;
+            }
+            Msg::Restore(id) => {
+                let batch = BatchRequest {
+                    ids: vec![id],
+                    action: BatchAction::Restore,
+                };
+                let callback = self.link.callback(Msg::RestoreDone);
+                match api::batch_ingredients(&batch, callback) {
+                    Ok(task) => self.restore_task = Some(task),
+                    Err(error) => self.restore_error = Some(error.to_string()),
+                }
+            }
+            Msg::RestoreDone(result) => {
+                self.restore_task = None;
+                self.restore_error = match result {
+                    Ok(outcomes) => outcomes.into_iter().find_map(|outcome| outcome.error),
+                    Err(error) => Some(error.to_string()),
+                };
+                self.link.send_message(Msg::Fetch);
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            self.link.send_message(Msg::Fetch)
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div>
+                <AppAnchor route=AppRoutes::IngredientsPage>
+                    <span class="text-sm text-indigo-600 hover:underline">{"← Ingredients"}</span>
+                </AppAnchor>
+                <h1 class="my-4 text-2xl font-bold">{"Recently deleted"}</h1>
+                {match &self.restore_error {
+                    Some(error) => html! { <p class="text-red-600">{error}</p> },
+                    None => html! {},
+                }}
+                {match &self.state {
+                    States::Initial | States::Fetching => html! { <h1> {"Fetching"} </h1> },
+                    States::Success { deleted } if deleted.is_empty() => html! {
+                        <p class="text-gray-600">{"Nothing was deleted recently."}</p>
+                    },
+                    States::Success { deleted } => html! {
+                        <ul class="divide-y divide-gray-200">
+                            {deleted.iter().map(|deleted| self.view_deleted(deleted)).collect::<Html>()}
+                        </ul>
+                    },
+                    States::Error(error) => html! {
+                        <>
+                            <h1 class="text-red-600 ">{error}</h1>
+                            <button
+                                class=button_styles
+                                onclick=self.link.callback(|_| Msg::Fetch)
+                            >
+                                {"Click to retry"}
+                            </button>
+                        </>
+                    },
+                }}
+            </div>
+        }
+    }
+}
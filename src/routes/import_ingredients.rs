@@ -0,0 +1,414 @@
+use crate::api::{self, ApiError, ImportOutcome, NewIngredient};
+use crate::components::button_styles;
+use crate::csv_import::{guess_mapping, import_rows, Column, ImportRow};
+use crate::routes::{AppAnchor, AppRoutes};
+use web_sys::{DragEvent, File};
+use whats_cooking_types::csv::parse_csv;
+use yew::services::reader::{FileData, ReaderService, ReaderTask};
+use yew::{
+    html, services::fetch::FetchTask, ChangeData, Component, ComponentLink, Html, ShouldRender,
+};
+
+/// Rows sent per request, so big files show progress and don't hit body limits.
+const BATCH_SIZE: usize = 100;
+/// Most rows shown in the preview, to keep big files responsive.
+const MAX_PREVIEW_ROWS: usize = 200;
+
+/// A CSV file read into memory, with the field each column is imported into.
+struct Upload {
+    file_name: String,
+    headers: Vec<String>,
+    mapping: Vec<Option<Column>>,
+    records: Vec<Vec<String>>,
+    rows: Vec<ImportRow>,
+}
+
+/// Progress of posting the valid rows of an upload.
+struct Progress {
+    rows: Vec<ImportRow>,
+    /// Rows whose batch was answered.
+    sent: usize,
+    /// Rows in the batch waiting for an answer.
+    batch: usize,
+    created: usize,
+    failures: Vec<String>,
+}
+
+impl Progress {
+    fn is_done(&self) -> bool {
+        self.sent >= self.rows.len()
+    }
+}
+
+/// Page seeding the catalog from a CSV file with one ingredient per row.
+pub struct ImportIngredientsPage {
+    upload: Option<Upload>,
+    progress: Option<Progress>,
+    error: Option<String>,
+    /// Whether a file is being dragged over the drop zone.
+    dragging: bool,
+    reader: ReaderService,
+    reader_task: Option<ReaderTask>,
+    fetch_task: Option<FetchTask>,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    PickFile(Option<File>),
+    Dragging(bool),
+    FileLoaded(FileData),
+    SetColumn(usize, Option<Column>),
+    Import,
+    BatchDone(Result<Vec<ImportOutcome>, ApiError>),
+    Reset,
+}
+
+impl ImportIngredientsPage {
+    fn send_batch(&mut self) {
+        let progress = match &mut self.progress {
+            Some(progress) if !progress.is_done() => progress,
+            _ => return,
+        };
+        let end = (progress.sent + BATCH_SIZE).min(progress.rows.len());
+        let batch: Vec<NewIngredient> = progress.rows[progress.sent..end]
+            .iter()
+            .map(|row| row.ingredient.clone())
+            .collect();
+        progress.batch = batch.len();
+        let callback = self.link.callback(Msg::BatchDone);
+        match api::import_ingredients(&batch, callback) {
+            // store the task so it isn't canceled immediately
+            Ok(task) => self.fetch_task = Some(task),
+            Err(error) => {
+                self.error = Some(error.to_string());
+                self.fetch_task = None;
+            }
+        }
+    }
+
+    fn view_drop_zone(&self) -> Html {
+        let border = if self.dragging {
+            "border-indigo-600 bg-indigo-50"
+        } else {
+            "border-gray-300"
+        };
+        html! {
+            <label
+                class=format!("flex flex-col items-center p-8 border-2 border-dashed rounded-md cursor-pointer {}", border)
+                ondragover=self.link.callback(|event: DragEvent| {
+                    // the browser only fires `drop` when `dragover` is canceled
+                    event.prevent_default();
+                    Msg::Dragging(true)
+                })
+                ondragleave=self.link.callback(|_| Msg::Dragging(false))
+                ondrop=self.link.callback(|event: DragEvent| {
+                    event.prevent_default();
+                    Msg::PickFile(event.data_transfer().and_then(|data| data.files()).and_then(|files| files.get(0)))
+                })
+            >
+                <span class="text-gray-600">{"Drop a CSV file here or click to pick one"}</span>
+                <span class="text-sm text-gray-500">
+                    {"Columns: name, name_scientific, decription, food_group, food_subgroup"}
+                </span>
+                <input
+                    class="hidden"
+                    type="file"
+                    accept=".csv,text/csv"
+                    onchange=self.link.callback(|event: ChangeData| match event {
+                        ChangeData::Files(files) => Msg::PickFile(files.get(0)),
+                        _ => Msg::PickFile(None),
+                    })
+                />
+            </label>
+        }
+    }
+
+    fn view_mapping(&self, upload: &Upload) -> Html {
+        html! {
+            <div class="flex flex-wrap my-4">
+                {upload.headers.iter().enumerate().map(|(index, header)| {
+                    let mapped = upload.mapping.get(index).copied().flatten();
+                    html! {
+                        <div class="flex flex-col mr-4 mb-2">
+                            <label class="ml-2 mb-1 text-sm text-gray-600">{header}</label>
+                            <select
+                                class="px-2 py-1 border-2 rounded-md border-gray-200"
+                                disabled=self.progress.is_some()
+                                onchange=self.link.callback(move |event: ChangeData| match event {
+                                    ChangeData::Select(select) => {
+                                        let value = select.value();
+                                        Msg::SetColumn(index, Column::ALL.iter().copied().find(|c| c.field() == value))
+                                    }
+                                    _ => Msg::SetColumn(index, None),
+                                })
+                            >
+                                <option value="" selected=mapped.is_none()>{"Ignore"}</option>
+                                {Column::ALL.iter().map(|column| html! {
+                                    <option value=column.field() selected=mapped == Some(*column)>
+                                        {column.field()}
+                                    </option>
+                                }).collect::<Html>()}
+                            </select>
+                        </div>
+                    }
+                }).collect::<Html>()}
+            </div>
+        }
+    }
+
+    fn view_row(row: &ImportRow) -> Html {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let class = if row.is_valid() { "" } else { "bg-red-50" };
+        html! {
+            <tr class=class>
+                <td class="px-2 text-gray-500">{row.row}</td>
+                <td class="px-2">{&row.ingredient.name}</td>
+                <td class="px-2">{optional(&row.ingredient.name_scientific)}</td>
+                <td class="px-2">{optional(&row.ingredient.description)}</td>
+                <td class="px-2">{&row.ingredient.food_group}</td>
+                <td class="px-2">{optional(&row.ingredient.food_subgroup)}</td>
+                <td class="px-2 text-red-600">{row.errors.join(", ")}</td>
+            </tr>
+        }
+    }
+
+    fn view_preview(&self, upload: &Upload) -> Html {
+        let valid = upload.rows.iter().filter(|row| row.is_valid()).count();
+        let invalid = upload.rows.len() - valid;
+        html! {
+            <div>
+                <p class="my-2">
+                    {format!("{}: {} rows ready to import", upload.file_name, valid)}
+                    {if invalid > 0 {
+                        html! { <span class="text-red-600">{format!(", {} with errors will be skipped", invalid)}</span> }
+                    } else {
+                        html! {}
+                    }}
+                </p>
+                <table class="text-sm text-left">
+                    <thead>
+                        <tr>
+                            <th class="px-2">{"Row"}</th>
+                            {Column::ALL.iter().map(|column| html! { <th class="px-2">{column.field()}</th> }).collect::<Html>()}
+                            <th class="px-2">{"Problems"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {upload.rows.iter().take(MAX_PREVIEW_ROWS).map(Self::view_row).collect::<Html>()}
+                    </tbody>
+                </table>
+                {if upload.rows.len() > MAX_PREVIEW_ROWS {
+                    html! {
+                        <p class="mt-2 text-sm text-gray-600">
+                            {format!("and {} more rows", upload.rows.len() - MAX_PREVIEW_ROWS)}
+                        </p>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        }
+    }
+
+    fn view_progress(progress: &Progress) -> Html {
+        let percent = if progress.rows.is_empty() {
+            100
+        } else {
+            progress.sent * 100 / progress.rows.len()
+        };
+        html! {
+            <div class="my-4">
+                <div class="w-full h-2 bg-gray-200 rounded">
+                    <div class="h-2 bg-indigo-600 rounded" style=format!("width: {}%", percent)></div>
+                </div>
+                <p class="mt-1 text-sm text-gray-600">
+                    {if progress.is_done() {
+                        format!("Imported {} ingredients", progress.created)
+                    } else {
+                        format!("Imported {} of {} rows", progress.sent, progress.rows.len())
+                    }}
+                </p>
+                <ul class="text-sm text-red-600">
+                    {progress.failures.iter().map(|failure| html! { <li>{failure}</li> }).collect::<Html>()}
+                </ul>
+            </div>
+        }
+    }
+}
+
+impl Component for ImportIngredientsPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            upload: None,
+            progress: None,
+            error: None,
+            dragging: false,
+            reader: ReaderService::new(),
+            reader_task: None,
+            fetch_task: None,
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::PickFile(file) => {
+                self.dragging = false;
+                let file = match file {
+                    Some(file) => file,
+                    None => return true,
+                };
+                let callback = self.link.callback(Msg::FileLoaded);
+                match self.reader.read_file(file, callback) {
+                    Ok(task) => self.reader_task = Some(task),
+                    Err(error) => self.error = Some(error.to_string()),
+                }
+            }
+            Msg::Dragging(dragging) => self.dragging = dragging,
+            Msg::FileLoaded(data) => {
+                self.reader_task = None;
+                self.progress = None;
+                let mut records = parse_csv(&String::from_utf8_lossy(&data.content));
+                if records.is_empty() {
+                    self.error = Some(format!("{} is empty", data.name));
+                    self.upload = None;
+                    return true;
+                }
+                let headers = records.remove(0);
+                let mapping = guess_mapping(&headers);
+                self.error = None;
+                self.upload = Some(Upload {
+                    file_name: data.name,
+                    rows: import_rows(&records, &mapping),
+                    headers,
+                    mapping,
+                    records,
+                });
+            }
+            Msg::SetColumn(index, column) => {
+                if let Some(upload) = &mut self.upload {
+                    for mapped in upload.mapping.iter_mut() {
+                        // a field is read from one column only
+                        if column.is_some() && *mapped == column {
+                            *mapped = None;
+                        }
+                    }
+                    if let Some(mapped) = upload.mapping.get_mut(index) {
+                        *mapped = column;
+                    }
+                    upload.rows = import_rows(&upload.records, &upload.mapping);
+                }
+            }
+            Msg::Import => {
+                let rows = match &self.upload {
+                    Some(upload) => upload
+                        .rows
+                        .iter()
+                        .filter(|row| row.is_valid())
+                        .cloned()
+                        .collect(),
+                    None => return false,
+                };
+                self.error = None;
+                self.progress = Some(Progress {
+                    rows,
+                    sent: 0,
+                    batch: 0,
+                    created: 0,
+                    failures: vec![],
+                });
+                self.send_batch();
+            }
+            Msg::BatchDone(result) => {
+                self.fetch_task = None;
+                let progress = match &mut self.progress {
+                    Some(progress) => progress,
+                    None => return false,
+                };
+                match result {
+                    // one outcome per row is expected, anything else can't be matched up with the rows
+                    Ok(outcomes) if outcomes.len() != progress.batch => {
+                        self.error = Some(format!(
+                            "The server answered {} results for {} rows, stopped importing",
+                            outcomes.len(),
+                            progress.batch
+                        ))
+                    }
+                    Ok(outcomes) => {
+                        let sent = &progress.rows[progress.sent..];
+                        for (row, outcome) in sent.iter().zip(&outcomes) {
+                            match &outcome.error {
+                                Some(error) => progress
+                                    .failures
+                                    .push(format!("Row {}: {}", row.row, error)),
+                                None => progress.created += 1,
+                            }
+                        }
+                        progress.sent += progress.batch;
+                        self.send_batch();
+                    }
+                    // the rows sent so far stay imported, so importing again would duplicate them
+                    Err(error) => self.error = Some(error.to_string()),
+                }
+            }
+            Msg::Reset => {
+                self.upload = None;
+                self.progress = None;
+                self.error = None;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let importing = self.fetch_task.is_some();
+        html! {
+            <div>
+                <AppAnchor route=AppRoutes::IngredientsPage>
+                    <span class="text-sm text-indigo-600 hover:underline">{"← Ingredients"}</span>
+                </AppAnchor>
+                <h1 class="my-4 text-2xl font-bold">{"Import ingredients"}</h1>
+                {match &self.error {
+                    Some(error) => html! { <p class="text-red-600">{error}</p> },
+                    None => html! {},
+                }}
+                {match &self.upload {
+                    None => self.view_drop_zone(),
+                    Some(upload) => html! {
+                        <>
+                            {self.view_mapping(upload)}
+                            {match &self.progress {
+                                Some(progress) => Self::view_progress(progress),
+                                None => html! {},
+                            }}
+                            <div class="flex space-x-2 my-2">
+                                <button
+                                    class=button_styles
+                                    disabled=importing || self.progress.is_some() || !upload.rows.iter().any(|row| row.is_valid())
+                                    onclick=self.link.callback(|_| Msg::Import)
+                                >
+                                    {"Import"}
+                                </button>
+                                <button
+                                    class=button_styles
+                                    disabled=importing
+                                    onclick=self.link.callback(|_| Msg::Reset)
+                                >
+                                    {"Pick another file"}
+                                </button>
+                            </div>
+                            {self.view_preview(upload)}
+                        </>
+                    },
+                }}
+            </div>
+        }
+    }
+}
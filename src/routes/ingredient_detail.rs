@@ -0,0 +1,132 @@
+use crate::api::{self, ApiError, IIngredient};
+use crate::components::button_styles;
+use crate::routes::{AppAnchor, AppRoutes};
+use yew::{
+    html, services::fetch::FetchTask, Component, ComponentLink, Html, Properties, ShouldRender,
+};
+
+enum States {
+    Fetching,
+    Success(IIngredient),
+    Error(String),
+}
+
+/// Page showing every field of a single ingredient at `/ingredients/{id}`.
+pub struct IngredientDetailPage {
+    props: Props,
+    state: States,
+    fetch_task: Option<FetchTask>,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    Fetch,
+    FetchSuccess(IIngredient),
+    FetchError(ApiError),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub id: i32,
+}
+
+impl Component for IngredientDetailPage {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            props,
+            state: States::Fetching,
+            fetch_task: None,
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Fetch => {
+                let callback = self.link.callback(|result| match result {
+                    Ok(ingredient) => Msg::FetchSuccess(ingredient),
+                    Err(error) => Msg::FetchError(error),
+                });
+                match api::get_ingredient(self.props.id, callback) {
+                    Ok(task) => {
+                        // store the task so it isn't canceled immediately
+                        self.fetch_task = Some(task);
+                        self.state = States::Fetching;
+                    }
+                    Err(error) => self.state = States::Error(error.to_string()),
+                }
+            }
+            Msg::FetchSuccess(ingredient) => {
+                self.state = States::Success(ingredient);
+                self.fetch_task = None;
+            }
+            Msg::FetchError(error) => {
+                self.state = States::Error(error.to_string());
+                self.fetch_task = None;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.id == self.props.id {
+            return false;
+        }
+        self.props = props;
+        self.link.send_message(Msg::Fetch);
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            self.link.send_message(Msg::Fetch)
+        }
+    }
+
+    fn view(&self) -> Html {
+        let field = |label: &str, value: &Option<String>| {
+            html! {
+                <div class="py-2">
+                    <dt class="text-sm text-gray-600">{label}</dt>
+                    <dd>{value.as_deref().unwrap_or("—")}</dd>
+                </div>
+            }
+        };
+
+        html! {
+            <div>
+                <AppAnchor classes="text-sm text-indigo-600" route=AppRoutes::IngredientsPage>
+                    {"← All ingredients"}
+                </AppAnchor>
+                {match &self.state {
+                    States::Fetching => html! { <h1> {"Fetching"} </h1> },
+                    States::Success(ingredient) => html! {
+                        <div class="mt-4">
+                            <h1 class="text-2xl font-bold">{&ingredient.name}</h1>
+                            <dl class="mt-2">
+                                {field("Scientific name", &ingredient.name_scientific)}
+                                {field("Food group", &ingredient.food_group)}
+                                {field("Food subgroup", &ingredient.food_subgroup)}
+                                {field("Description", &ingredient.description)}
+                            </dl>
+                        </div>
+                    },
+                    States::Error(error) => html! {
+                        <>
+                            <h1 class="text-red-600 ">{error}</h1>
+                            <button
+                                class=button_styles
+                                onclick=self.link.callback(|_| Msg::Fetch)
+                            >
+                                {"Click to retry"}
+                            </button>
+                        </>
+                    },
+                }}
+            </div>
+        }
+    }
+}
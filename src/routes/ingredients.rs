@@ -1,211 +1,748 @@
-use crate::components::{button_styles, Ingredient, Input, NewIngredientsForm};
-use serde::Deserialize;
+use crate::api::{
+    self, ApiError, BatchAction, BatchOutcome, BatchRequest, IIngredient, IngredientQuery,
+    IngredientSort, Page, SectionCount, SortField,
+};
+use crate::components::{
+    button_styles, download, BulkActions, ExportMenu, Ingredient, Input, NewIngredientsForm,
+    RenderRow, Toast, VirtualList,
+};
+use crate::export::{export_ingredients, Format};
+use crate::grouping::{grouped_rows, Row, SectionKey};
+use crate::routes::{AppAnchor, AppRoutes};
+use std::collections::{BTreeSet, HashSet};
+use std::rc::Rc;
+use std::time::Duration;
+use whats_cooking_types::search::{search, Highlights};
+use whats_cooking_types::taxonomy::Taxonomy;
 use yew::{
-    format::{Json, Nothing},
     html,
     prelude::*,
-    services::{
-        fetch::{FetchTask, Request, Response},
-        FetchService,
-    },
+    services::{fetch::FetchTask, timeout::TimeoutTask, TimeoutService},
     Component, ComponentLink, Html, InputData, ShouldRender,
 };
 
-#[derive(Deserialize, Debug)]
+/// Delay used when no `filter_debounce_ms` is passed.
+const DEFAULT_FILTER_DEBOUNCE_MS: u64 = 300;
+/// Number of ingredients fetched at once.
+const PAGE_SIZE: usize = 50;
+
+#[derive(Debug)]
 enum States {
     Initial,
     Fetching,
-    Success { ingredients: Vec<IIngredient> },
+    Success {
+        ingredients: Vec<IIngredient>,
+        /// Number of ingredients matching the filter, including the ones not fetched yet.
+        total: usize,
+        loading_more: bool,
+    },
     Error(String),
 }
 
-#[derive(Deserialize, Debug)]
-pub struct IIngredient {
-    pub id: i32,
-    pub name: String,
-    pub name_scientific: Option<String>,
-    pub decription: Option<String>,
-    pub food_group: Option<String>,
-    pub food_subgroup: Option<String>,
-}
-
-#[derive(Deserialize, Debug)]
-struct IngredientResponse {
-    response: Vec<IIngredient>,
+/// Deletion that can still be undone from the toast.
+struct Undo {
+    message: String,
+    ids: Vec<i32>,
 }
 
 pub struct IngredientsPage {
+    props: Props,
     state: States,
     current_filter: String,
+    /// Food group the list is limited to, empty for every group.
+    current_group: String,
+    groups: Vec<String>,
+    /// Ingredients matching the filter in each section of the grouped list,
+    /// fetched or not.
+    sections: Vec<SectionCount>,
+    sections_task: Option<FetchTask>,
+    /// Whether the list is split into food group and subgroup sections.
+    grouped: bool,
+    collapsed: HashSet<SectionKey>,
+    /// Ids of the ingredients selected for bulk actions.
+    selected: BTreeSet<i32>,
+    /// Ids of the ingredients open or being edited, kept mounted by the list.
+    busy: HashSet<i32>,
     fetch_task: Option<FetchTask>,
+    select_all_task: Option<FetchTask>,
+    /// Why selecting every matching ingredient failed, shown next to the button.
+    select_all_error: Option<String>,
+    export_task: Option<FetchTask>,
+    /// Why exporting the matching ingredients failed, shown next to the menu.
+    export_error: Option<String>,
+    groups_task: Option<FetchTask>,
+    undo: Option<Undo>,
+    undo_task: Option<FetchTask>,
+    /// Pending filter update, dropping it cancels the update.
+    filter_task: Option<TimeoutTask>,
     link: ComponentLink<Self>,
 }
 
 pub enum Msg {
+    /// Fetches the first page for the current filter and group.
     FetchIngredients,
-    FetchIngredientsSuccess(Vec<IIngredient>),
-    FetchIngredientsError(anyhow::Error),
+    LoadMore,
+    FetchIngredientsSuccess(Page<IIngredient>),
+    FetchIngredientsError(ApiError),
+    FetchGroups,
+    FetchGroupsSuccess(Vec<String>),
+    FetchSections,
+    FetchSectionsSuccess(Vec<SectionCount>),
+    SelectGroup(String),
+    ToggleGrouped,
+    ToggleSection(SectionKey),
+    ToggleSelected(i32),
+    SetBusy(i32, bool),
+    /// Selects every ingredient matching the filter, fetched or not.
+    SelectAll,
+    SelectAllSuccess(Vec<IIngredient>),
+    SelectAllError(ApiError),
+    ClearSelection,
+    BatchDone(Vec<BatchOutcome>),
+    IngredientDeleted(IIngredient),
+    BulkDeleted(Vec<i32>),
+    /// Restores the ingredients of the last deletion.
+    Undo,
+    UndoDone,
+    DismissUndo,
+    /// Raw keystrokes in the filter input, applied once the user stops typing.
+    FilterInput(String),
     UpdateFilter(String),
+    IngredientUpdated(IIngredient),
+    /// Exports every ingredient matching the filter, fetched or not.
+    Export(Format),
+    ExportSuccess(Format, Vec<IIngredient>),
+    ExportError(ApiError),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// How long to wait after the last keystroke before filtering the list.
+    #[prop_or(DEFAULT_FILTER_DEBOUNCE_MS)]
+    pub filter_debounce_ms: u64,
+    /// Order of the list, taken from the `sort` query parameter of the url.
+    #[prop_or_default]
+    pub sort: Option<IngredientSort>,
 }
 
 impl IngredientsPage {
-    fn debounce_filter_update(&self, new_filter: String) {
-        self.link.send_message(Msg::UpdateFilter(new_filter))
+    /// Schedules the filter update, replacing and so canceling any update still pending.
+    fn debounce_filter_update(&mut self, new_filter: String) {
+        let callback = self
+            .link
+            .callback(move |_| Msg::UpdateFilter(new_filter.clone()));
+        let delay = Duration::from_millis(self.props.filter_debounce_ms);
+        self.filter_task = Some(TimeoutService::spawn(delay, callback));
+    }
+
+    /// Query for every ingredient matching the current filter and group.
+    fn query(&self) -> IngredientQuery {
+        let optional = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        IngredientQuery {
+            q: optional(&self.current_filter),
+            group: optional(&self.current_group),
+            sort: self.props.sort,
+            ..IngredientQuery::default()
+        }
+    }
+
+    /// Fetches `PAGE_SIZE` ingredients matching the current filter and group,
+    /// starting at `offset`. A fetch still running is canceled.
+    fn fetch_page(&mut self, offset: usize) -> Result<(), ApiError> {
+        let query = IngredientQuery {
+            limit: Some(PAGE_SIZE),
+            offset: Some(offset),
+            ..self.query()
+        };
+        let callback = self.link.callback(|result| match result {
+            Ok(page) => Msg::FetchIngredientsSuccess(page),
+            Err(error) => Msg::FetchIngredientsError(error),
+        });
+        // store the task so it isn't canceled immediately
+        self.fetch_task = Some(api::search_ingredients(&query, callback)?);
+        Ok(())
+    }
+
+    /// Number of rows in the list, how to render them and the rows of busy
+    /// ingredients. The fetched ingredients are searched again to find what
+    /// to highlight, the server already ranked them the same way.
+    fn rows(&self, ingredients: &[IIngredient]) -> (usize, RenderRow, Vec<usize>) {
+        let mut hits: Vec<(IIngredient, Highlights)> = search(ingredients, &self.current_filter)
+            .into_iter()
+            .map(|hit| (hit.ingredient.clone(), hit.highlights))
+            .collect();
+        if let Some(sort) = self.props.sort {
+            // same stable sort as the server, so ties keep their relevance order
+            hits.sort_by(|(a, _), (b, _)| sort.compare(a, b));
+        }
+        let rows: Vec<Row> = if self.grouped {
+            let ingredients: Vec<&IIngredient> =
+                hits.iter().map(|(ingredient, _)| ingredient).collect();
+            grouped_rows(&ingredients, &self.sections, &self.collapsed)
+        } else {
+            (0..hits.len()).map(Row::Ingredient).collect()
+        };
+        let on_update = self.link.callback(Msg::IngredientUpdated);
+        let on_deleted = self.link.callback(Msg::IngredientDeleted);
+        let on_toggle = self.link.callback(Msg::ToggleSection);
+        let on_select = self.link.callback(Msg::ToggleSelected);
+        let on_busy = self.link.callback(|(id, busy)| Msg::SetBusy(id, busy));
+        let selected = self.selected.clone();
+        let count = rows.len();
+        let pinned = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| match row {
+                Row::Ingredient(index) => self.busy.contains(&hits[*index].0.id),
+                Row::Header { .. } => false,
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let render_row: RenderRow = Rc::new(move |index| match &rows[index] {
+            Row::Header {
+                key,
+                count,
+                collapsed,
+            } => {
+                let toggled = key.clone();
+                let (icon, name, class) = match &key.subgroup {
+                    Some(subgroup) => ("", subgroup, "pl-6 text-sm text-gray-700"),
+                    None => (
+                        Taxonomy::bundled().icon(Some(&key.group)),
+                        &key.group,
+                        "font-bold text-indigo-600",
+                    ),
+                };
+                html! {
+                    <button
+                        key=format!("{}/{}", key.group, key.subgroup.as_deref().unwrap_or(""))
+                        class=format!("flex w-full p-2 text-left {}", class)
+                        onclick=on_toggle.reform(move |_| toggled.clone())
+                    >
+                        {if *collapsed { "▸ " } else { "▾ " }}{icon}{" "}{name}{format!(" ({})", count)}
+                    </button>
+                }
+            }
+            Row::Ingredient(index) => {
+                let (ingredient, highlights) = &hits[*index];
+                let id = ingredient.id;
+                html! {
+                    <Ingredient
+                        key={ingredient.id}
+                        ingredient=ingredient.clone()
+                        highlights=highlights.clone()
+                        on_update=on_update.clone()
+                        on_deleted=on_deleted.clone()
+                        selected=selected.contains(&ingredient.id)
+                        on_select=Some(on_select.reform(move |_| id))
+                        on_busy=on_busy.reform(move |busy| (id, busy))
+                    />
+                }
+            }
+        });
+        (count, render_row, pinned)
+    }
+
+    /// Links sorting the list by each field, clicking the current one flips its direction.
+    fn view_sort_links(&self) -> Html {
+        let current = self.props.sort;
+        let link = |field: SortField, label: &str| {
+            let (arrow, next) = match current {
+                Some(sort) if sort.field == field => (
+                    if sort.descending { " ▼" } else { " ▲" },
+                    IngredientSort {
+                        field,
+                        descending: !sort.descending,
+                    },
+                ),
+                _ => (
+                    "",
+                    IngredientSort {
+                        field,
+                        descending: false,
+                    },
+                ),
+            };
+            html! {
+                <AppAnchor route=AppRoutes::SortedIngredientsPage(next)>
+                    <span class="hover:underline">{label}{arrow}</span>
+                </AppAnchor>
+            }
+        };
+        html! {
+            <div class="flex space-x-3">
+                <span>{"Sort by:"}</span>
+                {link(SortField::Name, "Name")}
+                {link(SortField::FoodGroup, "Food group")}
+                {link(SortField::FoodSubgroup, "Subgroup")}
+                {link(SortField::Id, "Id")}
+                {if current.is_some() {
+                    html! {
+                        <AppAnchor route=AppRoutes::IngredientsPage>
+                            <span class="hover:underline">{"Clear"}</span>
+                        </AppAnchor>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        }
+    }
+
+    /// The fetched ingredients with the actions on them.
+    fn view_list(&self, ingredients: &[IIngredient], total: usize, loading_more: bool) -> Html {
+        let (row_count, render_row, pinned) = self.rows(ingredients);
+        html! {
+            <>
+                <BulkActions
+                    selected=self.selected.iter().copied().collect::<Vec<_>>()
+                    on_done=self.link.callback(Msg::BatchDone)
+                    on_deleted=self.link.callback(Msg::BulkDeleted)
+                    on_clear=self.link.callback(|_| Msg::ClearSelection)
+                />
+                <div class="flex justify-between mb-2 text-sm text-gray-600">
+                    <p>
+                        {format!("Showing {} of {} ingredients ", ingredients.len(), total)}
+                        <button
+                            class="text-indigo-600"
+                            disabled=self.select_all_task.is_some()
+                            onclick=self.link.callback(|_| Msg::SelectAll)
+                        >
+                            {format!("Select all {}", total)}
+                        </button>
+                        {match &self.select_all_error {
+                            Some(error) => html! { <span class="ml-2 text-red-600">{error}</span> },
+                            None => html! {},
+                        }}
+                    </p>
+                    {self.view_sort_links()}
+                    <button class="text-indigo-600" onclick=self.link.callback(|_| Msg::ToggleGrouped)>
+                        {if self.grouped { "Show as a list" } else { "Group by food group" }}
+                    </button>
+                </div>
+                <div class="flex items-center">
+                    <ExportMenu
+                        on_export=self.link.callback(Msg::Export)
+                        disabled=self.export_task.is_some()
+                    />
+                    {match &self.export_error {
+                        Some(error) => html! { <span class="ml-2 text-sm text-red-600">{error}</span> },
+                        None => html! {},
+                    }}
+                </div>
+                <VirtualList
+                    row_count=row_count
+                    render_row=render_row
+                    pinned=pinned
+                    on_end_reached=self.link.callback(|_| Msg::LoadMore)
+                />
+                {if ingredients.len() < total {
+                    html! {
+                        <button
+                            class=button_styles
+                            disabled=loading_more
+                            onclick=self.link.callback(|_| Msg::LoadMore)
+                        >
+                            {if loading_more { "Loading..." } else { "Load more" }}
+                        </button>
+                    }
+                } else {
+                    html! {}
+                }}
+            </>
+        }
     }
 }
 
 impl Component for IngredientsPage {
     type Message = Msg;
-    type Properties = ();
+    type Properties = Props;
 
-    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         Self {
+            props,
             state: States::Initial,
             current_filter: String::from(""),
+            current_group: String::from(""),
+            groups: vec![],
+            sections: vec![],
+            sections_task: None,
+            grouped: false,
+            collapsed: HashSet::new(),
+            selected: BTreeSet::new(),
+            busy: HashSet::new(),
             fetch_task: None,
+            select_all_task: None,
+            select_all_error: None,
+            export_task: None,
+            export_error: None,
+            undo: None,
+            undo_task: None,
+            groups_task: None,
+            filter_task: None,
             link,
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
-        match self.state {
-            States::Initial => match msg {
-                Msg::FetchIngredients => {
-                    // do the fetch
-                    log::info!("getting location");
-                    let request = Request::get("http://localhost:8082/ingredients")
-                        .body(Nothing)
-                        .expect("Could not build request.");
-                    // 2. construct a callback
-                    let callback = self.link.callback(
-                        |response: Response<Json<Result<IngredientResponse, anyhow::Error>>>| {
-                            let Json(data) = response.into_body();
-                            log::info!("got data {:?}", data);
-                            match data {
-                                Ok(data) => Msg::FetchIngredientsSuccess(data.response),
-                                Err(error) => Msg::FetchIngredientsError(error),
-                            }
-                        },
-                    );
-                    // 3. pass the request and callback to the fetch service
-                    let task =
-                        FetchService::fetch(request, callback).expect("failed to start request");
-                    // 4. store the task so it isn't canceled immediately
-                    self.fetch_task = Some(task);
+        match msg {
+            // The filter can be typed into before the ingredients have loaded.
+            Msg::FilterInput(value) => {
+                self.debounce_filter_update(value);
+                false
+            }
+            Msg::UpdateFilter(value) => {
+                self.filter_task = None;
+                if value == self.current_filter {
+                    return false;
+                }
+                self.current_filter = value;
+                self.link.send_message(Msg::FetchIngredients);
+                false
+            }
+            Msg::SelectGroup(group) => {
+                self.current_group = group;
+                self.link.send_message(Msg::FetchIngredients);
+                false
+            }
+            Msg::ToggleGrouped => {
+                self.grouped = !self.grouped;
+                true
+            }
+            Msg::ToggleSection(key) => {
+                if !self.collapsed.remove(&key) {
+                    self.collapsed.insert(key);
+                }
+                true
+            }
+            Msg::ToggleSelected(id) => {
+                if !self.selected.remove(&id) {
+                    self.selected.insert(id);
+                }
+                true
+            }
+            Msg::SetBusy(id, busy) => {
+                if busy {
+                    self.busy.insert(id)
+                } else {
+                    self.busy.remove(&id)
+                }
+            }
+            Msg::SelectAll => {
+                let callback =
+                    self.link
+                        .callback(|result: Result<Page<IIngredient>, _>| match result {
+                            Ok(page) => Msg::SelectAllSuccess(page.items),
+                            Err(error) => Msg::SelectAllError(error),
+                        });
+                self.select_all_error = None;
+                match api::search_ingredients(&self.query(), callback) {
+                    Ok(task) => self.select_all_task = Some(task),
+                    Err(error) => self.select_all_error = Some(error.to_string()),
+                }
+                true
+            }
+            Msg::SelectAllSuccess(ingredients) => {
+                self.select_all_task = None;
+                self.selected.extend(ingredients.iter().map(|i| i.id));
+                true
+            }
+            Msg::SelectAllError(error) => {
+                log::error!("failed to select all ingredients: {}", error);
+                self.select_all_task = None;
+                self.select_all_error = Some(error.to_string());
+                true
+            }
+            Msg::ClearSelection => {
+                self.selected.clear();
+                true
+            }
+            Msg::BatchDone(outcomes) => {
+                // failed ingredients stay selected so the action can be retried
+                for outcome in outcomes.iter().filter(|o| o.error.is_none()) {
+                    self.selected.remove(&outcome.id);
+                }
+                self.link.send_message(Msg::FetchIngredients);
+                false
+            }
+            Msg::FetchIngredients => {
+                log::info!("getting ingredients");
+                self.link.send_message(Msg::FetchSections);
+                match self.fetch_page(0) {
                     // we want to redraw so that the page displays a 'fetching...' message to the user
-                    // so return 'true'
-                    self.state = States::Fetching;
+                    Ok(()) => self.state = States::Fetching,
+                    Err(error) => self.state = States::Error(error.to_string()),
+                }
+                true
+            }
+            Msg::LoadMore => match self.state {
+                States::Success {
+                    ref ingredients,
+                    total,
+                    loading_more: false,
+                } if ingredients.len() < total => {
+                    let offset = ingredients.len();
+                    match self.fetch_page(offset) {
+                        Ok(()) => {
+                            if let States::Success {
+                                ref mut loading_more,
+                                ..
+                            } = self.state
+                            {
+                                *loading_more = true;
+                            }
+                        }
+                        Err(error) => self.state = States::Error(error.to_string()),
+                    }
                     true
                 }
                 _ => false,
             },
-            States::Fetching => match msg {
-                Msg::FetchIngredientsSuccess(data) => {
-                    log::info!("in success data {:?}", data);
-                    self.state = States::Success { ingredients: data };
-                    true
+            Msg::FetchIngredientsSuccess(page) => {
+                log::info!("got {} of {} ingredients", page.items.len(), page.total);
+                self.fetch_task = None;
+                match self.state {
+                    States::Success {
+                        ref mut ingredients,
+                        ref mut total,
+                        ref mut loading_more,
+                    } if page.offset > 0 => {
+                        ingredients.truncate(page.offset);
+                        ingredients.extend(page.items);
+                        *total = page.total;
+                        *loading_more = false;
+                    }
+                    _ => {
+                        self.state = States::Success {
+                            ingredients: page.items,
+                            total: page.total,
+                            loading_more: false,
+                        }
+                    }
                 }
-                Msg::FetchIngredientsError(error) => {
-                    log::info!("got found error {:?}", error);
-                    // let error = String::from("There was an error");
-                    self.state = States::Error(error.to_string());
-                    self.fetch_task = None;
-                    true
+                true
+            }
+            Msg::FetchIngredientsError(error) => {
+                log::info!("got found error {:?}", error);
+                self.state = States::Error(error.to_string());
+                self.fetch_task = None;
+                true
+            }
+            Msg::FetchGroups => {
+                let callback = self.link.callback(|result| match result {
+                    Ok(groups) => Msg::FetchGroupsSuccess(groups),
+                    Err(error) => {
+                        log::error!("failed to fetch food groups: {}", error);
+                        Msg::FetchGroupsSuccess(vec![])
+                    }
+                });
+                match api::list_food_groups(callback) {
+                    Ok(task) => self.groups_task = Some(task),
+                    Err(error) => log::error!("failed to fetch food groups: {}", error),
                 }
-                _ => false,
-            },
-            States::Success { ingredients: _ } => match msg {
-                Msg::UpdateFilter(value) => {
-                    self.current_filter = value;
-                    true
+                false
+            }
+            Msg::FetchGroupsSuccess(groups) => {
+                self.groups = groups;
+                self.groups_task = None;
+                true
+            }
+            Msg::FetchSections => {
+                let callback = self.link.callback(|result| match result {
+                    Ok(sections) => Msg::FetchSectionsSuccess(sections),
+                    Err(error) => {
+                        log::error!("failed to count ingredients by section: {}", error);
+                        // the grouped list counts the fetched ingredients instead
+                        Msg::FetchSectionsSuccess(vec![])
+                    }
+                });
+                match api::list_sections(&self.query(), callback) {
+                    Ok(task) => self.sections_task = Some(task),
+                    Err(error) => log::error!("failed to count ingredients by section: {}", error),
                 }
-                _ => false,
-            },
-            States::Error(_) => match msg {
-                Msg::FetchIngredients => {
-                    self.state = States::Initial;
-                    self.link.send_message(Msg::FetchIngredients);
+                false
+            }
+            Msg::FetchSectionsSuccess(sections) => {
+                self.sections = sections;
+                self.sections_task = None;
+                self.grouped
+            }
+            Msg::IngredientDeleted(deleted) => {
+                if let States::Success {
+                    ref mut ingredients,
+                    ref mut total,
+                    ..
+                } = self.state
+                {
+                    ingredients.retain(|i| i.id != deleted.id);
+                    *total = total.saturating_sub(1);
+                }
+                self.link.send_message(Msg::FetchSections);
+                self.selected.remove(&deleted.id);
+                self.undo = Some(Undo {
+                    message: format!("Deleted {}", deleted.name),
+                    ids: vec![deleted.id],
+                });
+                true
+            }
+            Msg::BulkDeleted(ids) => {
+                if ids.is_empty() {
+                    return false;
+                }
+                self.undo = Some(Undo {
+                    message: format!("Deleted {} ingredients", ids.len()),
+                    ids,
+                });
+                true
+            }
+            Msg::Undo => {
+                let ids = match self.undo.take() {
+                    Some(undo) => undo.ids,
+                    None => return false,
+                };
+                let batch = BatchRequest {
+                    ids,
+                    action: BatchAction::Restore,
+                };
+                let callback = self
+                    .link
+                    .callback(|result: Result<Vec<BatchOutcome>, ApiError>| {
+                        if let Err(error) = result {
+                            log::error!("failed to restore ingredients: {}", error);
+                        }
+                        Msg::UndoDone
+                    });
+                match api::batch_ingredients(&batch, callback) {
+                    Ok(task) => self.undo_task = Some(task),
+                    Err(error) => log::error!("failed to restore ingredients: {}", error),
+                }
+                true
+            }
+            Msg::UndoDone => {
+                self.undo_task = None;
+                self.link.send_message(Msg::FetchIngredients);
+                false
+            }
+            Msg::DismissUndo => {
+                self.undo = None;
+                true
+            }
+            Msg::IngredientUpdated(updated) => match self.state {
+                States::Success {
+                    ref mut ingredients,
+                    ..
+                } => {
+                    if let Some(ingredient) = ingredients.iter_mut().find(|i| i.id == updated.id) {
+                        *ingredient = updated;
+                    }
+                    // the ingredient may have moved to another section
+                    self.link.send_message(Msg::FetchSections);
                     true
                 }
                 _ => false,
             },
-        };
-        true
+            Msg::Export(format) => {
+                let callback = self
+                    .link
+                    .callback(move |result: Result<Page<IIngredient>, _>| match result {
+                        Ok(page) => Msg::ExportSuccess(format, page.items),
+                        Err(error) => Msg::ExportError(error),
+                    });
+                self.export_error = None;
+                match api::search_ingredients(&self.query(), callback) {
+                    Ok(task) => self.export_task = Some(task),
+                    Err(error) => self.export_error = Some(error.to_string()),
+                }
+                true
+            }
+            Msg::ExportSuccess(format, ingredients) => {
+                self.export_task = None;
+                let contents = export_ingredients(&ingredients, format);
+                if let Err(error) = download("ingredients", format, &contents) {
+                    log::error!("could not download export: {:?}", error);
+                    self.export_error = Some(String::from("The export could not be saved"));
+                }
+                true
+            }
+            Msg::ExportError(error) => {
+                log::error!("failed to export ingredients: {}", error);
+                self.export_task = None;
+                self.export_error = Some(error.to_string());
+                true
+            }
+        }
     }
 
-    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let sort_changed = props.sort != self.props.sort;
+        self.props = props;
+        if sort_changed {
+            self.link.send_message(Msg::FetchIngredients);
+        }
         false
     }
 
     fn rendered(&mut self, first_render: bool) {
         if first_render == true {
-            self.link.send_message(Msg::FetchIngredients)
+            self.link.send_message(Msg::FetchIngredients);
+            self.link.send_message(Msg::FetchGroups);
         }
     }
 
     fn view(&self) -> Html {
         let handle_change = &self
             .link
-            .callback(|event: InputData| Msg::UpdateFilter(event.value));
+            .callback(|event: InputData| Msg::FilterInput(event.value));
 
         html! {
             <div>
                 <div class="md:flex">
                    <NewIngredientsForm />
                 </div>
+                <div class="flex space-x-4">
+                    <AppAnchor route=AppRoutes::ImportIngredientsPage>
+                        <span class="text-sm text-indigo-600 hover:underline">{"Import from CSV"}</span>
+                    </AppAnchor>
+                    <AppAnchor route=AppRoutes::DeletedIngredientsPage>
+                        <span class="text-sm text-indigo-600 hover:underline">{"Recently deleted"}</span>
+                    </AppAnchor>
+                </div>
 
-                <div class="flex flex-col my-3 h-auto mb-5">
+                <div class="flex my-3 h-auto mb-5 space-x-2">
                     <Input
+                        class="w-2/3"
                         name="search"
                         id="search"
                         label="Filter Ingredients"
                         input_type="text"
                         on_change=handle_change
                     />
+                    <div class="flex flex-col w-1/3">
+                        <label for="group" class="ml-2 mb-2 text-sm text-gray-600 dark:text-gray-400">{"Food group"}</label>
+                        <select
+                            id="group"
+                            class="px-2 py-2 border-2 rounded-md border-gray-200"
+                            onchange=self.link.callback(|event: ChangeData| match event {
+                                ChangeData::Select(select) => Msg::SelectGroup(select.value()),
+                                _ => Msg::SelectGroup(String::new()),
+                            })
+                        >
+                            <option value="" selected=self.current_group.is_empty()>{"All groups"}</option>
+                            {self.groups.iter().map(|group| html! {
+                                <option value=group selected=group == &self.current_group>{group}</option>
+                            }).collect::<Html>()}
+                        </select>
+                    </div>
                 </div>
 
                 {match &self.state {
                     States::Initial => html! { <h1> {"Initial"} </h1> },
                     States::Fetching => html! { <h1> {"Fetching"} </h1>},
                     States::Success {
-                        ingredients
-                    } => ingredients
-                    .iter()
-                    .filter(|ingredient: &&IIngredient| {
-                        if &self.current_filter == "" || &self.current_filter.len() <= &3 {
-                            return true;
-                        }
-                        let group = match &ingredient.food_group {
-                            Some(v) => &v,
-                            None => "."
-                        };
-
-                        let contains_name: bool = ingredient.name.to_lowercase().contains(&self.current_filter.to_lowercase());
-                        let contains_group: bool =  group.to_lowercase().contains(&self.current_filter.to_lowercase());
-
-                        if (contains_name) {
-                            log::info!("{} is contained in cn {}", &self.current_filter.len(), ingredient.name)
-                        }
-                        if (contains_group) {
-                            log::info!("{} is contained in group: {}", &self.current_filter, group)
-                        }
-                        return contains_name || contains_group;
-                    })
-                    .map(|ingredient: &IIngredient| {
-                        html! {
-                            <Ingredient
-                                id={ingredient.id}
-                                key={ingredient.id}
-                                name=&ingredient.name
-                                group=match &ingredient.food_group {
-                                    Some(v) => &v,
-                                    None => "."
-                                }
-                                description=&ingredient.decription
-                            />
-                        }
-                    })
-                    .collect(),
+                        ingredients,
+                        total,
+                        loading_more,
+                    } => self.view_list(ingredients, *total, *loading_more),
                     States::Error(error) => html! {
                         <>
                             <h1 class="text-red-600 ">{error}</h1>
@@ -218,6 +755,17 @@ impl Component for IngredientsPage {
                         </>
                     }
                 }}
+                {match &self.undo {
+                    Some(undo) => html! {
+                        <Toast
+                            message=&undo.message
+                            action_label="Undo"
+                            on_action=self.link.callback(|_| Msg::Undo)
+                            on_dismiss=self.link.callback(|_| Msg::DismissUndo)
+                        />
+                    },
+                    None => html! {},
+                }}
             </div>
         }
     }
@@ -1,16 +1,27 @@
+mod deleted_ingredients;
 mod home;
+mod import_ingredients;
+mod ingredient_detail;
 mod ingredients;
 mod iss;
+mod pantry;
+mod recipe_detail;
 mod recipes;
 
+use crate::api::IngredientSort;
 use yew_router::prelude::RouterAnchor;
 use yew_router::switch::Permissive;
 use yew_router::Switch;
 // use yew_router::matcher::MatcherToken;
 
+pub use deleted_ingredients::DeletedIngredientsPage;
 pub use home::Home;
+pub use import_ingredients::ImportIngredientsPage;
+pub use ingredient_detail::IngredientDetailPage;
 pub use ingredients::IngredientsPage;
 pub use iss::FetchServiceExample;
+pub use pantry::PantryPage;
+pub use recipe_detail::RecipeDetailPage;
 pub use recipes::RecipesPage;
 
 /// App routes
@@ -18,10 +29,23 @@ pub use recipes::RecipesPage;
 pub enum AppRoutes {
     #[to = "/iss"]
     FetchServiceExample,
+    #[to = "/recipes/{id}"]
+    RecipeDetailPage(i32),
     #[to = "/recipes"]
     RecipesPage,
+    // Must come before `/ingredients`, which would otherwise match it as a prefix.
+    #[to = "/ingredients/deleted"]
+    DeletedIngredientsPage,
+    #[to = "/ingredients/import"]
+    ImportIngredientsPage,
+    #[to = "/ingredients/{id}"]
+    IngredientDetailPage(i32),
+    #[to = "/ingredients?sort={sort}"]
+    SortedIngredientsPage(IngredientSort),
     #[to = "/ingredients"]
     IngredientsPage,
+    #[to = "/pantry"]
+    PantryPage,
     #[to = "/404"]
     NotFound(Permissive<String>),
     #[to = "/"]
@@ -0,0 +1,246 @@
+use crate::api::{self, ApiError, IIngredient, Recipe};
+use crate::components::{button_styles, Input};
+use crate::pantry::{rank_recipes, RecipeMatch};
+use crate::routes::{AppAnchor, AppRoutes};
+use std::collections::BTreeSet;
+use yew::{
+    html, services::fetch::FetchTask, Component, ComponentLink, Html, InputData, ShouldRender,
+};
+
+/// Most ingredients listed at once while picking, to keep big catalogs usable.
+const MAX_LISTED_INGREDIENTS: usize = 50;
+
+/// "What can I cook?" page ranking recipes by the ingredients the user has on hand.
+pub struct PantryPage {
+    ingredients: Option<Vec<IIngredient>>,
+    recipes: Option<Vec<Recipe>>,
+    error: Option<String>,
+    selected: BTreeSet<i32>,
+    current_filter: String,
+    fetch_tasks: Vec<FetchTask>,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    Fetch,
+    FetchIngredientsSuccess(Vec<IIngredient>),
+    FetchRecipesSuccess(Vec<Recipe>),
+    FetchError(ApiError),
+    UpdateFilter(String),
+    ToggleIngredient(i32),
+    ClearSelection,
+}
+
+impl PantryPage {
+    fn view_picker(&self, ingredients: &[IIngredient]) -> Html {
+        let filter = self.current_filter.to_lowercase();
+        let matching: Vec<&IIngredient> = ingredients
+            .iter()
+            .filter(|ingredient| ingredient.name.to_lowercase().contains(&filter))
+            .collect();
+
+        html! {
+            <div class="md:w-1/3">
+                <Input
+                    name="pantry-search"
+                    id="pantry-search"
+                    label="Find ingredients you have"
+                    value=&self.current_filter
+                    on_change=self.link.callback(|event: InputData| Msg::UpdateFilter(event.value))
+                />
+                <ul class="mt-2 space-y-1">
+                    {matching.iter().take(MAX_LISTED_INGREDIENTS).map(|ingredient| {
+                        let id = ingredient.id;
+                        html! {
+                            <li>
+                                <label class="flex items-center space-x-2">
+                                    <input
+                                        type="checkbox"
+                                        checked=self.selected.contains(&id)
+                                        onclick=self.link.callback(move |_| Msg::ToggleIngredient(id))
+                                    />
+                                    <span>{&ingredient.name}</span>
+                                </label>
+                            </li>
+                        }
+                    }).collect::<Html>()}
+                </ul>
+                {if matching.len() > MAX_LISTED_INGREDIENTS {
+                    html! {
+                        <p class="mt-2 text-sm text-gray-600">
+                            {format!("{} more, refine the search to see them", matching.len() - MAX_LISTED_INGREDIENTS)}
+                        </p>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        }
+    }
+
+    fn view_selection(&self, ingredients: &[IIngredient]) -> Html {
+        if self.selected.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="mb-4">
+                <div class="flex flex-wrap">
+                    {ingredients.iter().filter(|i| self.selected.contains(&i.id)).map(|ingredient| {
+                        let id = ingredient.id;
+                        html! {
+                            <button
+                                class="mr-2 mb-2 px-2 py-1 rounded-md bg-blue-100 text-sm"
+                                title="remove"
+                                onclick=self.link.callback(move |_| Msg::ToggleIngredient(id))
+                            >
+                                {&ingredient.name}{" ✕"}
+                            </button>
+                        }
+                    }).collect::<Html>()}
+                </div>
+                <button class="text-sm text-indigo-600" onclick=self.link.callback(|_| Msg::ClearSelection)>
+                    {"Clear all"}
+                </button>
+            </div>
+        }
+    }
+
+    fn view_match(recipe_match: &RecipeMatch) -> Html {
+        html! {
+            <li class="mb-4">
+                <AppAnchor route=AppRoutes::RecipeDetailPage(recipe_match.recipe.id)>
+                    <h3 class="text-lg font-semibold hover:underline">{&recipe_match.recipe.name}</h3>
+                </AppAnchor>
+                <p class="text-sm text-gray-600">
+                    {format!("You have {} of {} ingredients", recipe_match.covered, recipe_match.required())}
+                </p>
+                {if recipe_match.missing.is_empty() {
+                    html! { <p class="text-sm text-green-600">{"You have everything!"}</p> }
+                } else {
+                    html! {
+                        <p class="text-sm">
+                            {"Missing: "}
+                            {recipe_match.missing.iter().map(|line| line.name.as_str()).collect::<Vec<_>>().join(", ")}
+                        </p>
+                    }
+                }}
+            </li>
+        }
+    }
+
+    fn view_results(&self, ingredients: &[IIngredient], recipes: &[Recipe]) -> Html {
+        if self.selected.is_empty() {
+            return html! { <p class="text-gray-600">{"Pick the ingredients you have to see what you can cook."}</p> };
+        }
+        let pantry: Vec<IIngredient> = ingredients
+            .iter()
+            .filter(|i| self.selected.contains(&i.id))
+            .cloned()
+            .collect();
+        let matches = rank_recipes(recipes, &pantry);
+        if matches.is_empty() {
+            return html! { <p class="text-gray-600">{"No recipe uses any of these ingredients yet."}</p> };
+        }
+        html! {
+            <ul>{matches.iter().map(Self::view_match).collect::<Html>()}</ul>
+        }
+    }
+}
+
+impl Component for PantryPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            ingredients: None,
+            recipes: None,
+            error: None,
+            selected: BTreeSet::new(),
+            current_filter: String::new(),
+            fetch_tasks: vec![],
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Fetch => {
+                self.error = None;
+                let on_ingredients = self.link.callback(|result| match result {
+                    Ok(ingredients) => Msg::FetchIngredientsSuccess(ingredients),
+                    Err(error) => Msg::FetchError(error),
+                });
+                let on_recipes = self.link.callback(|result| match result {
+                    Ok(recipes) => Msg::FetchRecipesSuccess(recipes),
+                    Err(error) => Msg::FetchError(error),
+                });
+                let tasks = api::list_ingredients(on_ingredients)
+                    .and_then(|ingredients| Ok(vec![ingredients, api::list_recipes(on_recipes)?]));
+                match tasks {
+                    // store the tasks so they aren't canceled immediately
+                    Ok(tasks) => self.fetch_tasks = tasks,
+                    Err(error) => self.error = Some(error.to_string()),
+                }
+            }
+            Msg::FetchIngredientsSuccess(mut ingredients) => {
+                ingredients.sort_by_key(|ingredient| ingredient.name.to_lowercase());
+                self.ingredients = Some(ingredients);
+            }
+            Msg::FetchRecipesSuccess(recipes) => self.recipes = Some(recipes),
+            Msg::FetchError(error) => {
+                self.error = Some(error.to_string());
+                self.fetch_tasks.clear();
+            }
+            Msg::UpdateFilter(value) => self.current_filter = value,
+            Msg::ToggleIngredient(id) => {
+                if !self.selected.remove(&id) {
+                    self.selected.insert(id);
+                }
+            }
+            Msg::ClearSelection => self.selected.clear(),
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            self.link.send_message(Msg::Fetch)
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div>
+                <h1 class="mb-4 text-2xl font-bold">{"What can I cook?"}</h1>
+                {match (&self.error, &self.ingredients, &self.recipes) {
+                    (Some(error), _, _) => html! {
+                        <>
+                            <h1 class="text-red-600 ">{error}</h1>
+                            <button
+                                class=button_styles
+                                onclick=self.link.callback(|_| Msg::Fetch)
+                            >
+                                {"Click to retry"}
+                            </button>
+                        </>
+                    },
+                    (None, Some(ingredients), Some(recipes)) => html! {
+                        <div class="md:flex md:space-x-6">
+                            {self.view_picker(ingredients)}
+                            <div class="md:w-2/3 mt-4 md:mt-0">
+                                {self.view_selection(ingredients)}
+                                {self.view_results(ingredients, recipes)}
+                            </div>
+                        </div>
+                    },
+                    _ => html! { <h1> {"Fetching"} </h1> },
+                }}
+            </div>
+        }
+    }
+}
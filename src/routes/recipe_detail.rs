@@ -0,0 +1,223 @@
+use crate::api::{self, ApiError, Recipe, RecipeIngredient};
+use crate::components::button_styles;
+use crate::routes::{AppAnchor, AppRoutes};
+use whats_cooking_types::format_quantity;
+use yew::{
+    html, services::fetch::FetchTask, Component, ComponentLink, Html, Properties, ShouldRender,
+};
+
+enum States {
+    Fetching,
+    Success(Recipe),
+    Error(String),
+}
+
+/// Page showing a full recipe with its ingredients and steps at `/recipes/{id}`.
+pub struct RecipeDetailPage {
+    props: Props,
+    state: States,
+    fetch_task: Option<FetchTask>,
+    link: ComponentLink<Self>,
+}
+
+pub enum Msg {
+    Fetch,
+    FetchSuccess(Recipe),
+    FetchError(ApiError),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub id: i32,
+}
+
+/// Formats a duration in minutes as e.g. "1 h 15 min".
+fn format_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{} min", m),
+        (h, 0) => format!("{} h", h),
+        (h, m) => format!("{} h {} min", h, m),
+    }
+}
+
+fn view_ingredient(ingredient: &RecipeIngredient) -> Html {
+    let amount = [
+        ingredient.quantity.map(format_quantity),
+        ingredient.unit.clone(),
+    ]
+    .iter()
+    .flatten()
+    .filter(|part| !part.is_empty())
+    .cloned()
+    .collect::<Vec<_>>()
+    .join(" ");
+    let note = match &ingredient.note {
+        Some(note) if !note.is_empty() => format!(", {}", note),
+        _ => String::new(),
+    };
+    html! {
+        <li>
+            {if amount.is_empty() { html! {} } else { html! { <span class="font-semibold">{amount}{" "}</span> } }}
+            {match ingredient.ingredient_id {
+                Some(id) => html! {
+                    <AppAnchor classes="text-indigo-600 hover:underline" route=AppRoutes::IngredientDetailPage(id)>
+                        {&ingredient.name}
+                    </AppAnchor>
+                },
+                None => html! { {&ingredient.name} },
+            }}
+            <span class="text-gray-600">{note}</span>
+        </li>
+    }
+}
+
+fn view_recipe(recipe: &Recipe) -> Html {
+    let timings = vec![
+        ("Prep", recipe.prep_time),
+        ("Cook", recipe.cook_time),
+        ("Total", recipe.total_time()),
+    ];
+    html! {
+        <div class="mt-4">
+            {match &recipe.url {
+                Some(url) if !url.is_empty() => html! {
+                    <img class="rounded-lg md:w-1/2 object-cover" src=url alt=&recipe.name />
+                },
+                _ => html! {},
+            }}
+            <h1 class="mt-4 text-2xl font-bold">{&recipe.name}</h1>
+            <p class="mt-2 text-gray-600">{recipe.description.as_deref().unwrap_or("")}</p>
+            <dl class="mt-4 flex space-x-6">
+                {match &recipe.yields {
+                    Some(yields) if !yields.is_empty() => html! {
+                        <div>
+                            <dt class="text-sm text-gray-600">{"Yields"}</dt>
+                            <dd>{yields}</dd>
+                        </div>
+                    },
+                    _ => html! {},
+                }}
+                {timings
+                    .into_iter()
+                    .filter_map(|(label, minutes)| minutes.map(|m| (label, m)))
+                    .map(|(label, minutes)| html! {
+                        <div>
+                            <dt class="text-sm text-gray-600">{label}</dt>
+                            <dd>{format_minutes(minutes)}</dd>
+                        </div>
+                    })
+                    .collect::<Html>()}
+            </dl>
+            <h2 class="mt-6 mb-2 uppercase tracking-wide text-sm text-indigo-600 font-bold">{"Ingredients"}</h2>
+            {if recipe.ingredients.is_empty() {
+                html! { <p class="text-gray-600">{"No ingredients listed."}</p> }
+            } else {
+                html! {
+                    <ul class="list-disc ml-6 space-y-1">
+                        {recipe.ingredients.iter().map(view_ingredient).collect::<Html>()}
+                    </ul>
+                }
+            }}
+            <h2 class="mt-6 mb-2 uppercase tracking-wide text-sm text-indigo-600 font-bold">{"Steps"}</h2>
+            {if recipe.steps.is_empty() {
+                html! { <p class="text-gray-600">{"No steps listed."}</p> }
+            } else {
+                html! {
+                    <ol class="list-decimal ml-6 space-y-2">
+                        {recipe.steps.iter().map(|step| html! { <li>{step}</li> }).collect::<Html>()}
+                    </ol>
+                }
+            }}
+            {match &recipe.link {
+                Some(link) if !link.is_empty() => html! {
+                    <a href=link.as_str() target="_blank" rel="noopener noreferrer" class="mt-6 block text-sm text-indigo-600 hover:underline">
+                        {"View original recipe"}
+                    </a>
+                },
+                _ => html! {},
+            }}
+        </div>
+    }
+}
+
+impl Component for RecipeDetailPage {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            props,
+            state: States::Fetching,
+            fetch_task: None,
+            link,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Fetch => {
+                let callback = self.link.callback(|result| match result {
+                    Ok(recipe) => Msg::FetchSuccess(recipe),
+                    Err(error) => Msg::FetchError(error),
+                });
+                match api::get_recipe(self.props.id, callback) {
+                    Ok(task) => {
+                        // store the task so it isn't canceled immediately
+                        self.fetch_task = Some(task);
+                        self.state = States::Fetching;
+                    }
+                    Err(error) => self.state = States::Error(error.to_string()),
+                }
+            }
+            Msg::FetchSuccess(recipe) => {
+                self.state = States::Success(recipe);
+                self.fetch_task = None;
+            }
+            Msg::FetchError(error) => {
+                self.state = States::Error(error.to_string());
+                self.fetch_task = None;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.id == self.props.id {
+            return false;
+        }
+        self.props = props;
+        self.link.send_message(Msg::Fetch);
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            self.link.send_message(Msg::Fetch)
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div>
+                <AppAnchor classes="text-sm text-indigo-600" route=AppRoutes::RecipesPage>
+                    {"← All recipes"}
+                </AppAnchor>
+                {match &self.state {
+                    States::Fetching => html! { <h1> {"Fetching"} </h1> },
+                    States::Success(recipe) => view_recipe(recipe),
+                    States::Error(error) => html! {
+                        <>
+                            <h1 class="text-red-600 ">{error}</h1>
+                            <button
+                                class=button_styles
+                                onclick=self.link.callback(|_| Msg::Fetch)
+                            >
+                                {"Click to retry"}
+                            </button>
+                        </>
+                    },
+                }}
+            </div>
+        }
+    }
+}
@@ -1,19 +1,79 @@
-use crate::components::NewRecipesForm;
-use yew::{html, Component, ComponentLink, Html, ShouldRender};
+use crate::api::{self, ApiError, Recipe};
+use crate::components::{button_styles, download, ExportMenu, NewRecipesForm, RecipeCard};
+use crate::export::{export_recipes, Format};
+use yew::{html, services::fetch::FetchTask, Component, ComponentLink, Html, ShouldRender};
+
+enum States {
+    Initial,
+    Fetching,
+    Success { recipes: Vec<Recipe> },
+    Error(String),
+}
 
 pub struct RecipesPage {
+    state: States,
+    fetch_task: Option<FetchTask>,
     link: ComponentLink<Self>,
 }
 
+pub enum Msg {
+    Fetch,
+    FetchSuccess(Vec<Recipe>),
+    FetchError(ApiError),
+    Export(Format),
+}
+
 impl Component for RecipesPage {
-    type Message = ();
+    type Message = Msg;
     type Properties = ();
 
     fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        Self { link }
+        Self {
+            state: States::Initial,
+            fetch_task: None,
+            link,
+        }
     }
 
-    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Fetch => {
+                log::info!("getting recipes");
+                let callback = self.link.callback(|result| match result {
+                    Ok(recipes) => Msg::FetchSuccess(recipes),
+                    Err(error) => Msg::FetchError(error),
+                });
+                match api::list_recipes(callback) {
+                    Ok(task) => {
+                        // store the task so it isn't canceled immediately
+                        self.fetch_task = Some(task);
+                        // keep showing the current list while a new recipe is being loaded in
+                        if let States::Success { .. } = self.state {
+                            return false;
+                        }
+                        self.state = States::Fetching;
+                    }
+                    Err(error) => self.state = States::Error(error.to_string()),
+                }
+            }
+            Msg::FetchSuccess(recipes) => {
+                self.state = States::Success { recipes };
+                self.fetch_task = None;
+            }
+            Msg::FetchError(error) => {
+                self.state = States::Error(error.to_string());
+                self.fetch_task = None;
+            }
+            Msg::Export(format) => {
+                if let States::Success { recipes } = &self.state {
+                    let contents = export_recipes(recipes, format);
+                    if let Err(error) = download("recipes", format, &contents) {
+                        log::error!("could not download export: {:?}", error);
+                    }
+                }
+                return false;
+            }
+        }
         true
     }
 
@@ -21,26 +81,46 @@ impl Component for RecipesPage {
         false
     }
 
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            self.link.send_message(Msg::Fetch)
+        }
+    }
+
     fn view(&self) -> Html {
         html! {
             <div>
                 <h1>{"Recipes page"}</h1>
-                <div class="md:flex">
-                    <div class="md:flex-shrink-0">
-                        <img class="rounded-lg md:w-56" src="https://images.unsplash.com/photo-1556740738-b6a63e27c4df?ixlib=rb-1.2.1&ixid=eyJhcHBfaWQiOjEyMDd9&auto=format&fit=crop&w=448&q=80" width="448" height="299" alt="Woman paying for a purchase" />
-                    </div>
-                    <div class="mt-4 md:mt-0 md:ml-6">
-                        <div class="uppercase tracking-wide text-sm text-indigo-600 font-bold">{"Marketing"}</div>
-                        <a href="/iss" class="block mt-1 text-lg leading-tight font-semibold text-gray-900 hover:underline">{"Finding customers for your new business"}</a>
-                        <p class="mt-2 text-gray-600">{"Getting a new business off the ground is a lot of hard work. Here are five ideas you can use to find your first customers."}</p>
-                    </div>
-                </div>
-                <div class="md:flex">
-                    <button>{"Hello"}</button>
-                </div>
+                {match &self.state {
+                    States::Initial => html! { <h1> {"Initial"} </h1> },
+                    States::Fetching => html! { <h1> {"Fetching"} </h1> },
+                    States::Success { recipes } if recipes.is_empty() => html! {
+                        <p class="mt-2 text-gray-600">{"No recipes yet, add one below."}</p>
+                    },
+                    States::Success { recipes } => html! {
+                        <>
+                            <ExportMenu on_export=self.link.callback(Msg::Export) />
+                            {recipes
+                                .iter()
+                                .map(|recipe| html! { <RecipeCard key={recipe.id} recipe=recipe.clone() /> })
+                                .collect::<Html>()}
+                        </>
+                    },
+                    States::Error(error) => html! {
+                        <>
+                            <h1 class="text-red-600 ">{error}</h1>
+                            <button
+                                class=button_styles
+                                onclick=self.link.callback(|_| Msg::Fetch)
+                            >
+                                {"Click to retry"}
+                            </button>
+                        </>
+                    }
+                }}
 
                 <div class="flex">
-                    <NewRecipesForm />
+                    <NewRecipesForm on_created=self.link.callback(|_| Msg::Fetch) />
                 </div>
             </div>
         }
@@ -0,0 +1,140 @@
+//! Picking the options a combobox suggests for what has been typed so far.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Suggestion {
+    /// One of the existing options.
+    Existing(String),
+    /// Adds what was typed as a new value.
+    Create(String),
+}
+
+impl Suggestion {
+    pub fn value(&self) -> &str {
+        match self {
+            Suggestion::Existing(value) | Suggestion::Create(value) => value,
+        }
+    }
+}
+
+/// Up to `limit` of `options` matching `query`, the ones starting with it
+/// first, ignoring case. When `allow_create` is set and no option is exactly
+/// `query`, creating it is offered last.
+pub fn suggestions(
+    options: &[String],
+    query: &str,
+    limit: usize,
+    allow_create: bool,
+) -> Vec<Suggestion> {
+    let query = query.trim();
+    let lower = query.to_lowercase();
+    let (mut starting, containing): (Vec<&String>, Vec<&String>) = options
+        .iter()
+        .filter(|option| option.to_lowercase().contains(&lower))
+        .partition(|option| option.to_lowercase().starts_with(&lower));
+    starting.extend(containing);
+    with_create(starting, options, query, limit, allow_create)
+}
+
+/// Up to `limit` of `options` in their order, for options already matched
+/// against `query` elsewhere, e.g. by a search on the server. Creating
+/// `query` is offered like in [`suggestions`].
+pub fn matched_suggestions(
+    options: &[String],
+    query: &str,
+    limit: usize,
+    allow_create: bool,
+) -> Vec<Suggestion> {
+    with_create(
+        options.iter().collect(),
+        options,
+        query.trim(),
+        limit,
+        allow_create,
+    )
+}
+
+fn with_create(
+    matching: Vec<&String>,
+    options: &[String],
+    query: &str,
+    limit: usize,
+    allow_create: bool,
+) -> Vec<Suggestion> {
+    let mut suggestions: Vec<Suggestion> = matching
+        .into_iter()
+        .take(limit)
+        .map(|option| Suggestion::Existing(option.clone()))
+        .collect();
+    let exists = options
+        .iter()
+        .any(|option| option.eq_ignore_ascii_case(query));
+    if allow_create && !query.is_empty() && !exists {
+        suggestions.push(Suggestion::Create(query.to_string()));
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matched_suggestions, suggestions, Suggestion};
+
+    fn options() -> Vec<String> {
+        [
+            "Fruits",
+            "Herbs and Spices",
+            "Dishes",
+            "Snack foods",
+            "Fish",
+        ]
+        .iter()
+        .map(|option| option.to_string())
+        .collect()
+    }
+
+    fn existing(value: &str) -> Suggestion {
+        Suggestion::Existing(String::from(value))
+    }
+
+    #[test]
+    fn suggests_options_starting_with_the_query_first() {
+        assert_eq!(
+            suggestions(&options(), "fi", 10, true),
+            vec![existing("Fish"), Suggestion::Create(String::from("fi"))]
+        );
+        assert_eq!(
+            suggestions(&options(), "S", 2, false),
+            vec![existing("Snack foods"), existing("Fruits")]
+        );
+    }
+
+    #[test]
+    fn offers_to_create_only_new_values() {
+        assert_eq!(
+            suggestions(&options(), " fish ", 10, true),
+            vec![existing("Fish")]
+        );
+        assert_eq!(
+            suggestions(&options(), "Insects", 10, true),
+            vec![Suggestion::Create(String::from("Insects"))]
+        );
+        assert!(suggestions(&options(), "Insects", 10, false).is_empty());
+        assert_eq!(suggestions(&options(), "", 2, true).len(), 2);
+    }
+
+    #[test]
+    fn keeps_matched_options_as_they_are() {
+        let matched = vec![String::from("Potato"), String::from("Sweet potato")];
+        assert_eq!(
+            matched_suggestions(&matched, "potatoe", 10, true),
+            vec![
+                existing("Potato"),
+                existing("Sweet potato"),
+                Suggestion::Create(String::from("potatoe"))
+            ]
+        );
+        assert_eq!(
+            matched_suggestions(&matched, "potato ", 1, true),
+            vec![existing("Potato")]
+        );
+    }
+}
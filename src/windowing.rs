@@ -0,0 +1,64 @@
+//! Working out which rows of a long scrolling list need to be rendered.
+use std::ops::Range;
+
+/// Rows of a list whose rows are `heights` pixels tall that are visible in a
+/// viewport `viewport_height` pixels tall scrolled down by `scroll_top`
+/// pixels, plus `buffer` rows on either side so fast scrolling doesn't show
+/// blank space.
+pub fn visible_rows(
+    scroll_top: f64,
+    viewport_height: f64,
+    heights: &[f64],
+    buffer: usize,
+) -> Range<usize> {
+    let scroll_top = scroll_top.max(0.0);
+    let bottom = scroll_top + viewport_height.max(0.0);
+    let count = heights.len();
+    let mut first = count;
+    let mut last = count;
+    let mut top = 0.0;
+    for (index, height) in heights.iter().enumerate() {
+        if top >= bottom {
+            last = index;
+            break;
+        }
+        if first == count && top + height > scroll_top {
+            first = index;
+        }
+        top += height;
+    }
+    let start = first.saturating_sub(buffer);
+    let end = (last + buffer).min(count);
+    start..end.max(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::visible_rows;
+
+    #[test]
+    fn renders_the_visible_rows_and_a_buffer() {
+        let heights = vec![40.0; 10_000];
+        assert_eq!(visible_rows(0.0, 400.0, &heights, 5), 0..15);
+        assert_eq!(visible_rows(4_000.0, 400.0, &heights, 5), 95..115);
+        // part way through a row
+        assert_eq!(visible_rows(4_020.0, 400.0, &heights, 5), 95..116);
+    }
+
+    #[test]
+    fn uses_the_height_of_each_row() {
+        let mut heights = vec![40.0; 100];
+        heights[2] = 400.0;
+        assert_eq!(visible_rows(0.0, 400.0, &heights, 0), 0..3);
+        assert_eq!(visible_rows(300.0, 400.0, &heights, 0), 2..9);
+        assert_eq!(visible_rows(300.0, 400.0, &heights, 1), 1..10);
+    }
+
+    #[test]
+    fn stays_within_the_list() {
+        assert_eq!(visible_rows(0.0, 400.0, &[40.0; 3], 5), 0..3);
+        assert_eq!(visible_rows(1_000_000.0, 400.0, &[40.0; 100], 5), 95..100);
+        assert_eq!(visible_rows(-50.0, 400.0, &[40.0; 100], 5), 0..15);
+        assert_eq!(visible_rows(0.0, 400.0, &[], 5), 0..0);
+    }
+}
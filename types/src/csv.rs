@@ -0,0 +1,62 @@
+//! Minimal CSV reading shared by the import page and the FooDB importer.
+
+/// Splits CSV `text` into records of fields.
+///
+/// Follows RFC 4180: fields may be wrapped in double quotes to hold commas,
+/// line breaks or doubled `""` quotes. Blank lines and a leading byte order
+/// mark are skipped.
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') | (false, '\r') => {
+                record.push(std::mem::take(&mut field));
+                push_record(&mut records, std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    record.push(field);
+    push_record(&mut records, record);
+    records
+}
+
+fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push(record);
+    }
+}
+
+/// Joins `records` into CSV text, quoting the fields that need it so
+/// [`parse_csv`] reads them back unchanged.
+pub fn write_csv(records: &[Vec<String>]) -> String {
+    let mut text = String::new();
+    for record in records {
+        let fields: Vec<String> = record
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        text.push_str(&fields.join(","));
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,397 @@
+//! Parsing free-text ingredient lines such as "2 1/2 cups finely chopped
+//! onions" into a quantity, unit, name and preparation note, and resolving
+//! the name against the ingredient catalog.
+use crate::search::edit_distance;
+use crate::{format_quantity, Ingredient, RecipeIngredient};
+
+/// Lowest confidence at which [`match_catalog`] suggests an ingredient.
+pub const MIN_CONFIDENCE: f64 = 0.5;
+
+/// Units as written in recipes, with the spelling they are stored as. Plurals
+/// are recognised by dropping a trailing "s" or "es".
+const UNITS: &[(&str, &[&str])] = &[
+    ("cup", &["cup", "c"]),
+    ("tbsp", &["tbsp", "tbs", "tbl", "tablespoon"]),
+    ("tsp", &["tsp", "teaspoon"]),
+    ("g", &["g", "gr", "gram", "gramme"]),
+    ("kg", &["kg", "kilo", "kilogram", "kilogramme"]),
+    ("mg", &["mg", "milligram"]),
+    ("ml", &["ml", "milliliter", "millilitre"]),
+    ("l", &["l", "liter", "litre"]),
+    ("oz", &["oz", "ounce"]),
+    ("fl oz", &["floz"]),
+    ("lb", &["lb", "pound"]),
+    ("pint", &["pint", "pt"]),
+    ("quart", &["quart", "qt"]),
+    ("gallon", &["gallon", "gal"]),
+    ("pinch", &["pinch"]),
+    ("dash", &["dash"]),
+    ("clove", &["clove"]),
+    ("can", &["can", "tin"]),
+    ("slice", &["slice"]),
+    ("bunch", &["bunch"]),
+    ("handful", &["handful"]),
+    ("sprig", &["sprig"]),
+    ("stick", &["stick"]),
+    ("piece", &["piece", "pc"]),
+    ("package", &["package", "pkg", "packet"]),
+];
+
+/// Words describing how an ingredient is prepared or its size, which go to
+/// the note when they come before the name. Adverbs ending in "ly", like
+/// "finely", are included too.
+const PREPARATION_WORDS: &[&str] = &[
+    "chopped",
+    "diced",
+    "minced",
+    "sliced",
+    "grated",
+    "shredded",
+    "crushed",
+    "peeled",
+    "melted",
+    "softened",
+    "beaten",
+    "sifted",
+    "ground",
+    "toasted",
+    "cooked",
+    "drained",
+    "rinsed",
+    "halved",
+    "quartered",
+    "cubed",
+    "packed",
+    "large",
+    "medium",
+    "small",
+];
+
+const FRACTIONS: &[(char, &str)] = &[
+    ('½', "1/2"),
+    ('⅓', "1/3"),
+    ('⅔', "2/3"),
+    ('¼', "1/4"),
+    ('¾', "3/4"),
+    ('⅕', "1/5"),
+    ('⅖', "2/5"),
+    ('⅗', "3/5"),
+    ('⅘', "4/5"),
+    ('⅙', "1/6"),
+    ('⅚', "5/6"),
+    ('⅛', "1/8"),
+    ('⅜', "3/8"),
+    ('⅝', "5/8"),
+    ('⅞', "7/8"),
+];
+
+/// The parts of a free-text ingredient line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParsedLine {
+    pub quantity: Option<f64>,
+    /// Upper end of a range such as "2-3 cloves garlic", `quantity` being the lower end.
+    pub quantity_max: Option<f64>,
+    /// Unit in its stored spelling, e.g. "tbsp" for "Tablespoons".
+    pub unit: Option<String>,
+    pub name: String,
+    /// How the ingredient is prepared, e.g. "finely chopped".
+    pub note: Option<String>,
+}
+
+impl ParsedLine {
+    /// The line as a recipe ingredient, linked to `ingredient` from the
+    /// catalog if given. A range is kept in the note since only one
+    /// quantity is stored.
+    pub fn into_recipe_ingredient(self, ingredient: Option<&Ingredient>) -> RecipeIngredient {
+        let range = match (self.quantity, self.quantity_max) {
+            (Some(min), Some(max)) => Some(format!(
+                "{} to {}",
+                format_quantity(min),
+                format_quantity(max)
+            )),
+            _ => None,
+        };
+        let note: Vec<String> = range.into_iter().chain(self.note).collect();
+        RecipeIngredient {
+            ingredient_id: ingredient.map(|ingredient| ingredient.id),
+            name: ingredient.map_or(self.name, |ingredient| ingredient.name.clone()),
+            quantity: self.quantity,
+            unit: self.unit,
+            note: Some(note.join(", ")).filter(|note| !note.is_empty()),
+        }
+    }
+}
+
+/// Spells unicode fractions and dashes the ascii way and puts spaces around
+/// ranges and between numbers and units, e.g. "2½-3tbsp" becomes "2 1/2 - 3 tbsp".
+fn normalize(line: &str) -> String {
+    let mut text = String::new();
+    let mut previous = ' ';
+    for c in line.chars() {
+        if let Some((_, fraction)) = FRACTIONS.iter().find(|(f, _)| *f == c) {
+            text.push(' ');
+            text.push_str(fraction);
+            previous = '0';
+            continue;
+        }
+        let c = match c {
+            '⁄' => '/',
+            '–' | '—' => '-',
+            c => c,
+        };
+        if c == '-' && previous.is_ascii_digit() {
+            text.push_str(" - ");
+        } else {
+            if text.ends_with(|c: char| c.is_ascii_digit()) && c.is_alphabetic() {
+                text.push(' ');
+            }
+            text.push(c);
+        }
+        if !c.is_whitespace() {
+            previous = c;
+        }
+    }
+    text
+}
+
+/// A number written as "2", "1.5", "1,5" or "1/2".
+fn parse_number(token: &str) -> Option<f64> {
+    match token.split_once('/') {
+        Some((numerator, denominator)) => {
+            let denominator: f64 = denominator.parse().ok()?;
+            Some(numerator.parse::<f64>().ok()? / denominator).filter(|n| n.is_finite())
+        }
+        None if token.starts_with(|c: char| c.is_ascii_digit()) => {
+            token.replace(',', ".").parse().ok()
+        }
+        None => None,
+    }
+}
+
+/// A quantity starting at `tokens[0]`, including mixed numbers like "2 1/2",
+/// with the number of tokens it spans.
+fn parse_quantity(tokens: &[&str]) -> Option<(f64, usize)> {
+    let whole = parse_number(tokens.first()?)?;
+    match tokens.get(1) {
+        Some(next) if !tokens[0].contains(['/', '.', ',']) && next.contains('/') => {
+            match parse_number(next) {
+                Some(fraction) if fraction < 1.0 => Some((whole + fraction, 2)),
+                _ => Some((whole, 1)),
+            }
+        }
+        _ => Some((whole, 1)),
+    }
+}
+
+/// The stored spelling of the unit `word`, if it is one.
+fn parse_unit(word: &str) -> Option<&'static str> {
+    let word = word.trim_end_matches('.').to_lowercase();
+    let singular = [
+        word.as_str(),
+        word.strip_suffix("es").unwrap_or(""),
+        word.strip_suffix('s').unwrap_or(""),
+    ];
+    UNITS
+        .iter()
+        .find(|(_, spellings)| {
+            singular
+                .iter()
+                .any(|word| !word.is_empty() && spellings.contains(word))
+        })
+        .map(|(unit, _)| *unit)
+}
+
+fn is_preparation_word(word: &str) -> bool {
+    let word = word.to_lowercase();
+    PREPARATION_WORDS.contains(&word.as_str())
+        // adverbs like "finely", but not nouns like "jelly"
+        || (word.len() > 3 && word.ends_with("ly") && !word.ends_with("lly"))
+}
+
+/// Splits a free-text ingredient line into its parts.
+///
+/// Quantities may be whole, decimal, fractions (also as unicode `½`), mixed
+/// numbers such as "2 1/2" and ranges such as "2-3" or "2 to 3". Words after a
+/// comma, in parentheses or describing the preparation before the name go
+/// to the note.
+pub fn parse_line(line: &str) -> ParsedLine {
+    let normalized = normalize(line);
+    // parentheses, e.g. "(about 200 g)", only ever hold a note
+    let mut notes = vec![];
+    let mut text = String::new();
+    let mut rest = normalized.as_str();
+    while let Some(open) = rest.find('(') {
+        let close = rest[open..]
+            .find(')')
+            .map_or(rest.len(), |close| open + close);
+        text.push_str(&rest[..open]);
+        notes.push(rest[open + 1..close].trim().to_string());
+        rest = rest.get(close + 1..).unwrap_or("");
+    }
+    text.push_str(rest);
+
+    // a comma between digits is a decimal one, as in "1,5 kg"
+    let chars: Vec<char> = text.chars().collect();
+    let comma = chars.iter().enumerate().position(|(i, c)| {
+        *c == ','
+            && !(i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+    });
+    let (main, after_comma) = match comma {
+        Some(comma) => (
+            chars[..comma].iter().collect::<String>(),
+            Some(
+                chars[comma + 1..]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_string(),
+            ),
+        ),
+        None => (text, None),
+    };
+    let tokens: Vec<&str> = main.split_whitespace().collect();
+    let mut position = 0;
+
+    let mut parsed = ParsedLine::default();
+    if let Some((quantity, used)) = parse_quantity(&tokens) {
+        parsed.quantity = Some(quantity);
+        position += used;
+        let range = tokens.get(position).map(|t| t.to_lowercase());
+        if matches!(range.as_deref(), Some("-") | Some("to") | Some("or")) {
+            if let Some((max, used)) = parse_quantity(&tokens[position + 1..]) {
+                parsed.quantity_max = Some(max);
+                position += 1 + used;
+            }
+        }
+    } else if matches!(
+        tokens.first().map(|t| t.to_lowercase()).as_deref(),
+        Some("a") | Some("an")
+    ) && tokens.get(1).and_then(|t| parse_unit(t)).is_some()
+    {
+        // "a pinch of salt"
+        parsed.quantity = Some(1.0);
+        position += 1;
+    }
+
+    // without a quantity, "cup" or "l" are more likely part of the name
+    if parsed.quantity.is_some() {
+        let two_words: String = tokens[position..].iter().take(2).copied().collect();
+        if parse_unit(&two_words) == Some("fl oz") {
+            parsed.unit = Some(String::from("fl oz"));
+            position += 2;
+        } else if let Some(unit) = tokens.get(position).and_then(|t| parse_unit(t)) {
+            parsed.unit = Some(String::from(unit));
+            position += 1;
+        }
+    }
+    if tokens.get(position).map(|t| t.to_lowercase()).as_deref() == Some("of") {
+        position += 1;
+    }
+
+    let mut preparation = vec![];
+    while let Some(word) = tokens.get(position) {
+        let joins_preparation = !preparation.is_empty()
+            && word.eq_ignore_ascii_case("and")
+            && tokens
+                .get(position + 1)
+                .is_some_and(|next| is_preparation_word(next));
+        if !is_preparation_word(word) && !joins_preparation {
+            break;
+        }
+        preparation.push(*word);
+        position += 1;
+    }
+    if position == tokens.len() && !preparation.is_empty() {
+        // nothing left for the name, e.g. "2 large", so the words were the name
+        position -= preparation.len();
+        preparation.clear();
+    }
+
+    parsed.name = tokens[position..].join(" ");
+    let preparation = preparation.join(" ");
+    let notes: Vec<String> = std::iter::once(preparation)
+        .chain(after_comma)
+        .chain(notes)
+        .filter(|note| !note.is_empty())
+        .collect();
+    parsed.note = Some(notes.join(", ")).filter(|note| !note.is_empty());
+    parsed
+}
+
+/// An ingredient of the catalog a line's name was resolved to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatalogMatch<'a> {
+    pub ingredient: &'a Ingredient,
+    /// From 0 to 1, 1 being an exact match of the name.
+    pub confidence: f64,
+}
+
+/// Lowercase words of `name` in the singular, for comparing names.
+fn name_words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let word = word.to_lowercase();
+            if let Some(stem) = word.strip_suffix("ies") {
+                format!("{}y", stem)
+            } else if let Some(stem) = word.strip_suffix("oes") {
+                format!("{}o", stem)
+            } else if word.ends_with('s') && !word.ends_with("ss") && word.len() > 3 {
+                word[..word.len() - 1].to_string()
+            } else {
+                word
+            }
+        })
+        .collect()
+}
+
+/// How sure we are that `words` and `candidate` name the same ingredient.
+fn confidence(words: &[String], candidate: &[String]) -> f64 {
+    if candidate.is_empty() {
+        return 0.0;
+    }
+    if words == candidate {
+        return 1.0;
+    }
+    let shared = candidate.iter().filter(|word| words.contains(word)).count();
+    // one name contains the other, e.g. "red onion" and "onion"
+    let contained = if shared == candidate.len() || shared == words.len() {
+        0.5 + 0.4 * shared as f64 / words.len().max(candidate.len()) as f64
+    } else {
+        0.0
+    };
+    // typos such as "tomatoe" or "parsely"
+    let a: Vec<char> = words.join(" ").chars().collect();
+    let b: Vec<char> = candidate.join(" ").chars().collect();
+    let longest = a.len().max(b.len());
+    let distance = edit_distance(&a, &b);
+    let typos = if distance <= (longest / 4).max(1) {
+        0.9 * (1.0 - distance as f64 / longest as f64)
+    } else {
+        0.0
+    };
+    contained.max(typos)
+}
+
+/// The catalog ingredient `name` most likely refers to, or `None` when no
+/// ingredient reaches [`MIN_CONFIDENCE`]. Ties go to the shorter name.
+pub fn match_catalog<'a>(name: &str, catalog: &'a [Ingredient]) -> Option<CatalogMatch<'a>> {
+    let words = name_words(name);
+    if words.is_empty() {
+        return None;
+    }
+    catalog
+        .iter()
+        .map(|ingredient| CatalogMatch {
+            ingredient,
+            confidence: confidence(&words, &name_words(&ingredient.name)),
+        })
+        .filter(|candidate| candidate.confidence >= MIN_CONFIDENCE)
+        .max_by(|a, b| {
+            a.confidence
+                .total_cmp(&b.confidence)
+                .then_with(|| b.ingredient.name.len().cmp(&a.ingredient.name.len()))
+        })
+}
@@ -0,0 +1,394 @@
+//! Serde models shared by the Whats Cooking frontend and backend, so the wire
+//! format is defined in exactly one place, along with the ingredient search
+//! both of them run.
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+pub mod csv;
+pub mod ingredient_line;
+pub mod search;
+pub mod taxonomy;
+
+/// Every successful response body is wrapped in a `{ "response": ... }` envelope.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Envelope<T> {
+    pub response: T,
+}
+
+impl<T> Envelope<T> {
+    pub fn new(response: T) -> Self {
+        Self { response }
+    }
+}
+
+/// Body of every failed response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Ingredient {
+    pub id: i32,
+    pub name: String,
+    pub name_scientific: Option<String>,
+    // The field has always been spelled this way on the wire.
+    #[serde(rename = "decription")]
+    pub description: Option<String>,
+    pub food_group: Option<String>,
+    pub food_subgroup: Option<String>,
+    /// Id of the same food in FooDB, for ingredients imported from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foodb_id: Option<i32>,
+}
+
+/// Query parameters of `GET /ingredients`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct IngredientQuery {
+    /// Free text searched in the names, groups and descriptions, best matches first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    /// Only ingredients of this food group, ignoring case.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Most ingredients returned, all of them when missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    /// Order of the ingredients, by relevance when searching and by id otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<IngredientSort>,
+}
+
+impl IngredientQuery {
+    /// Encodes the query as a url query string including the leading `?`,
+    /// or an empty string when nothing is set.
+    pub fn to_query_string(&self) -> String {
+        let mut pairs = vec![];
+        if let Some(q) = &self.q {
+            pairs.push(format!("q={}", encode_component(q)));
+        }
+        if let Some(group) = &self.group {
+            pairs.push(format!("group={}", encode_component(group)));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(format!("limit={}", limit));
+        }
+        if let Some(offset) = self.offset {
+            pairs.push(format!("offset={}", offset));
+        }
+        if let Some(sort) = &self.sort {
+            pairs.push(format!("sort={}", sort));
+        }
+        if pairs.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", pairs.join("&"))
+        }
+    }
+}
+
+/// Field the ingredients list can be sorted by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortField {
+    Id,
+    Name,
+    FoodGroup,
+    FoodSubgroup,
+}
+
+impl SortField {
+    pub const ALL: [SortField; 4] = [
+        SortField::Name,
+        SortField::FoodGroup,
+        SortField::FoodSubgroup,
+        SortField::Id,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortField::Id => "id",
+            SortField::Name => "name",
+            SortField::FoodGroup => "food_group",
+            SortField::FoodSubgroup => "food_subgroup",
+        }
+    }
+}
+
+/// Sort order of the ingredients list, written as the field name with a
+/// leading `-` when descending, e.g. `name` or `-food_group`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IngredientSort {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl IngredientSort {
+    /// Orders two ingredients. Ingredients without a value for the field
+    /// come last either way.
+    pub fn compare(&self, a: &Ingredient, b: &Ingredient) -> Ordering {
+        let text = |value: &Option<String>| value.as_deref().map(str::to_lowercase);
+        let ordering = match self.field {
+            SortField::Id => a.id.cmp(&b.id),
+            SortField::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortField::FoodGroup => {
+                return self.compare_optional(text(&a.food_group), text(&b.food_group))
+            }
+            SortField::FoodSubgroup => {
+                return self.compare_optional(text(&a.food_subgroup), text(&b.food_subgroup))
+            }
+        };
+        self.directed(ordering)
+    }
+
+    fn compare_optional(&self, a: Option<String>, b: Option<String>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => self.directed(a.cmp(&b)),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        }
+    }
+
+    fn directed(&self, ordering: Ordering) -> Ordering {
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+impl fmt::Display for IngredientSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.descending {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.field.as_str())
+    }
+}
+
+impl FromStr for IngredientSort {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (descending, name) = match value.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, value),
+        };
+        SortField::ALL
+            .iter()
+            .find(|field| field.as_str() == name)
+            .map(|&field| IngredientSort { field, descending })
+            .ok_or_else(|| format!("Can't sort ingredients by {}", name))
+    }
+}
+
+impl TryFrom<String> for IngredientSort {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<IngredientSort> for String {
+    fn from(sort: IngredientSort) -> Self {
+        sort.to_string()
+    }
+}
+
+/// Percent encodes everything but unreserved characters.
+fn encode_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// One page of a longer list.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of items matching the query across every page.
+    pub total: usize,
+    /// Position of the first item in `items` among all matching items.
+    pub offset: usize,
+}
+
+impl<T> Page<T> {
+    /// Whether there are matching items after this page.
+    pub fn has_more(&self) -> bool {
+        self.offset + self.items.len() < self.total
+    }
+}
+
+/// Number of ingredients sharing a food group and subgroup, as listed by
+/// `GET /ingredients/sections` for every ingredient matching a query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SectionCount {
+    pub food_group: Option<String>,
+    pub food_subgroup: Option<String>,
+    pub count: usize,
+}
+
+/// Body of `POST /ingredients`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NewIngredient {
+    pub name: String,
+    pub food_group: String,
+    #[serde(rename = "decription")]
+    pub description: Option<String>,
+    pub name_scientific: Option<String>,
+    pub food_subgroup: Option<String>,
+}
+
+/// An ingredient in the recently deleted list.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DeletedIngredient {
+    pub ingredient: Ingredient,
+    /// Seconds since the unix epoch.
+    pub deleted_at: u64,
+}
+
+/// Body of `POST /ingredients/batch`, applying one action to many ingredients.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchRequest {
+    pub ids: Vec<i32>,
+    pub action: BatchAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchAction {
+    /// Moves the ingredients to the recently deleted list, from where they can be restored.
+    Delete,
+    /// Brings recently deleted ingredients back.
+    Restore,
+    /// Moves the ingredients to another food group and subgroup.
+    SetFoodGroup {
+        food_group: String,
+        /// Subgroups are left as they are when missing.
+        #[serde(default)]
+        food_subgroup: Option<String>,
+    },
+}
+
+/// What happened to one ingredient of a batch request.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchOutcome {
+    pub id: i32,
+    /// Why the action failed for this ingredient, `None` when it succeeded.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// What happened to one row of an ingredient import, in the order the rows were sent.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ImportOutcome {
+    /// Id of the created ingredient, `None` when the row was rejected.
+    #[serde(default)]
+    pub id: Option<i32>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// An ingredient as used by a recipe, e.g. "2 cup basil, finely chopped".
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct RecipeIngredient {
+    /// Id of the matching ingredient in the catalog, if it has one.
+    pub ingredient_id: Option<i32>,
+    pub name: String,
+    #[serde(default)]
+    pub quantity: Option<f64>,
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// How the ingredient is prepared, e.g. "finely chopped".
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Formats a quantity without trailing zeros, e.g. `2`, `0.5` or `1.25`.
+pub fn format_quantity(quantity: f64) -> String {
+    let formatted = format!("{:.2}", quantity);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+impl fmt::Display for RecipeIngredient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(quantity) = self.quantity {
+            write!(f, "{} ", format_quantity(quantity))?;
+        }
+        match &self.unit {
+            Some(unit) if !unit.is_empty() => write!(f, "{} ", unit)?,
+            _ => {}
+        }
+        write!(f, "{}", self.name)?;
+        match &self.note {
+            Some(note) if !note.is_empty() => write!(f, ", {}", note),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Recipe {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub ingredients: Vec<RecipeIngredient>,
+    /// Preparation steps in the order they are done.
+    #[serde(default)]
+    pub steps: Vec<String>,
+    /// How much the recipe makes, e.g. "4 servings".
+    #[serde(default)]
+    pub yields: Option<String>,
+    /// In minutes.
+    #[serde(default)]
+    pub prep_time: Option<u32>,
+    /// In minutes.
+    #[serde(default)]
+    pub cook_time: Option<u32>,
+}
+
+impl Recipe {
+    /// Preparation and cooking time combined, in minutes.
+    pub fn total_time(&self) -> Option<u32> {
+        match (self.prep_time, self.cook_time) {
+            (None, None) => None,
+            (prep, cook) => Some(prep.unwrap_or(0).saturating_add(cook.unwrap_or(0))),
+        }
+    }
+}
+
+/// Body of `POST /recipes`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NewRecipe {
+    pub name: String,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub ingredients: Vec<RecipeIngredient>,
+    #[serde(default)]
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub yields: Option<String>,
+    #[serde(default)]
+    pub prep_time: Option<u32>,
+    #[serde(default)]
+    pub cook_time: Option<u32>,
+}
@@ -0,0 +1,270 @@
+//! Ranked fuzzy search over the ingredient catalog.
+//!
+//! Queries are split into words and every word has to match a word in one of
+//! the searched fields, either exactly, as a prefix, as a substring or with a
+//! small number of typos. Matches in the name count the most, and matches at
+//! the start of the name get an extra boost.
+use crate::Ingredient;
+use std::ops::Range;
+
+/// Fields of an ingredient that are searched, in order of importance.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Field {
+    Name,
+    FoodGroup,
+    FoodSubgroup,
+    NameScientific,
+    Description,
+}
+
+impl Field {
+    const ALL: [Field; 5] = [
+        Field::Name,
+        Field::FoodGroup,
+        Field::FoodSubgroup,
+        Field::NameScientific,
+        Field::Description,
+    ];
+
+    /// Multiplier, in tenths, applied to matches in this field.
+    fn weight(self) -> u32 {
+        match self {
+            Field::Name => 10,
+            Field::FoodGroup | Field::FoodSubgroup => 6,
+            Field::NameScientific => 5,
+            Field::Description => 3,
+        }
+    }
+
+    fn value(self, ingredient: &Ingredient) -> &str {
+        let value = match self {
+            Field::Name => return &ingredient.name,
+            Field::FoodGroup => &ingredient.food_group,
+            Field::FoodSubgroup => &ingredient.food_subgroup,
+            Field::NameScientific => &ingredient.name_scientific,
+            Field::Description => &ingredient.description,
+        };
+        value.as_deref().unwrap_or("")
+    }
+}
+
+/// Byte ranges of each field that matched the query, for highlighting.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Highlights {
+    pub name: Vec<Range<usize>>,
+    pub food_group: Vec<Range<usize>>,
+    pub food_subgroup: Vec<Range<usize>>,
+    pub name_scientific: Vec<Range<usize>>,
+    pub description: Vec<Range<usize>>,
+}
+
+impl Highlights {
+    fn field_mut(&mut self, field: Field) -> &mut Vec<Range<usize>> {
+        match field {
+            Field::Name => &mut self.name,
+            Field::FoodGroup => &mut self.food_group,
+            Field::FoodSubgroup => &mut self.food_subgroup,
+            Field::NameScientific => &mut self.name_scientific,
+            Field::Description => &mut self.description,
+        }
+    }
+}
+
+/// An ingredient matching the query.
+#[derive(Debug)]
+pub struct Hit<'a> {
+    pub ingredient: &'a Ingredient,
+    pub score: u32,
+    pub highlights: Highlights,
+}
+
+/// A word of a searched field, with the byte offset of every char so
+/// matches on the lowercased text can be mapped back onto the original.
+struct Word {
+    lower: Vec<char>,
+    offsets: Vec<usize>,
+    end: usize,
+}
+
+impl Word {
+    /// Byte range in the original text covering `chars` chars from `start`.
+    fn range(&self, start: usize, chars: usize) -> Range<usize> {
+        let end = self.offsets.get(start + chars).copied().unwrap_or(self.end);
+        self.offsets[start]..end
+    }
+}
+
+fn words(text: &str) -> Vec<Word> {
+    let mut words = vec![];
+    let mut current: Option<Word> = None;
+    for (offset, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            let word = current.get_or_insert_with(|| Word {
+                lower: vec![],
+                offsets: vec![],
+                end: 0,
+            });
+            word.lower.push(c.to_lowercase().next().unwrap_or(c));
+            word.offsets.push(offset);
+            word.end = offset + c.len_utf8();
+        } else if let Some(word) = current.take() {
+            words.push(word);
+        }
+    }
+    words.extend(current);
+    words
+}
+
+/// Number of typos tolerated in a query word of `len` chars.
+fn allowed_typos(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions
+/// and swaps of adjacent chars each count as one edit.
+pub fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Scores how well `token` matches `word`, returning the score and the
+/// matched range of chars within the word.
+fn match_word(token: &[char], word: &Word) -> Option<(u32, Range<usize>)> {
+    let lower = &word.lower;
+    if lower.as_slice() == token {
+        return Some((100, 0..lower.len()));
+    }
+    if lower.starts_with(token) {
+        return Some((80, 0..token.len()));
+    }
+    if let Some(start) = lower.windows(token.len()).position(|w| w == token) {
+        return Some((50, start..start + token.len()));
+    }
+    let allowed = allowed_typos(token.len());
+    if allowed == 0 {
+        return None;
+    }
+    // Comparing against the start of the word too lets half typed words with a typo match.
+    let prefix = &lower[..token.len().min(lower.len())];
+    let (distance, matched) = std::cmp::min(
+        (edit_distance(token, lower), lower.len()),
+        (edit_distance(token, prefix), prefix.len()),
+    );
+    if distance > allowed {
+        return None;
+    }
+    Some((50 - 10 * distance as u32, 0..matched))
+}
+
+/// Searches `ingredients` for `query`, best matches first.
+///
+/// An empty query matches everything in the original order.
+pub fn search<'a>(ingredients: &'a [Ingredient], query: &str) -> Vec<Hit<'a>> {
+    let tokens: Vec<Vec<char>> = words(query).into_iter().map(|word| word.lower).collect();
+    if tokens.is_empty() {
+        return ingredients
+            .iter()
+            .map(|ingredient| Hit {
+                ingredient,
+                score: 0,
+                highlights: Highlights::default(),
+            })
+            .collect();
+    }
+    let query = query.trim().to_lowercase();
+
+    let mut hits: Vec<Hit> = ingredients
+        .iter()
+        .filter_map(|ingredient| {
+            let fields: Vec<(Field, Vec<Word>)> = Field::ALL
+                .iter()
+                .map(|&field| (field, words(field.value(ingredient))))
+                .collect();
+            let mut score = 0;
+            let mut highlights = Highlights::default();
+            for token in &tokens {
+                let mut best: Option<(u32, Field, Range<usize>)> = None;
+                for (field, words) in &fields {
+                    for (index, word) in words.iter().enumerate() {
+                        let (base, chars) = match match_word(token, word) {
+                            Some(found) => found,
+                            None => continue,
+                        };
+                        let boost = if *field == Field::Name && index == 0 {
+                            20
+                        } else {
+                            0
+                        };
+                        let token_score = base * field.weight() / 10 + boost;
+                        let better = match &best {
+                            Some((s, _, _)) => token_score > *s,
+                            None => true,
+                        };
+                        if better {
+                            let range = word.range(chars.start, chars.len());
+                            best = Some((token_score, *field, range));
+                        }
+                    }
+                }
+                // every word of the query has to match somewhere
+                let (token_score, field, range) = best?;
+                score += token_score;
+                highlights.field_mut(field).push(range);
+            }
+            let name = ingredient.name.to_lowercase();
+            if name == query {
+                score += 50;
+            } else if name.starts_with(&query) {
+                score += 30;
+            }
+            Some(Hit {
+                ingredient,
+                score,
+                highlights,
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.ingredient.name.cmp(&b.ingredient.name))
+    });
+    hits
+}
+
+/// Sorts `ranges` and merges the ones that overlap or touch.
+pub fn merge_ranges(ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = vec![];
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
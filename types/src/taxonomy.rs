@@ -0,0 +1,95 @@
+//! Food groups and subgroups known to the app, with the icon and colour each
+//! group is shown with.
+//!
+//! The taxonomy is bundled from `data/taxonomy.json` and follows the FooDB
+//! classification. Groups outside of it are still allowed, they are just
+//! shown with a generic icon.
+use crate::NewIngredient;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Icon of ingredients whose group isn't in the taxonomy.
+pub const UNKNOWN_ICON: &str = "🍽️";
+/// Colour of ingredients whose group isn't in the taxonomy.
+pub const UNKNOWN_COLOR: &str = "#9ca3af";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Taxonomy {
+    pub groups: Vec<FoodGroup>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FoodGroup {
+    pub name: String,
+    pub icon: String,
+    /// CSS colour, e.g. `#22c55e`.
+    pub color: String,
+    pub subgroups: Vec<String>,
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+impl FoodGroup {
+    /// Finds a subgroup of this group, ignoring case.
+    pub fn subgroup(&self, name: &str) -> Option<&str> {
+        self.subgroups
+            .iter()
+            .find(|subgroup| same_name(subgroup, name))
+            .map(String::as_str)
+    }
+}
+
+impl Taxonomy {
+    /// The taxonomy bundled with the app, parsed on first use.
+    pub fn bundled() -> &'static Taxonomy {
+        static BUNDLED: OnceLock<Taxonomy> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            serde_json::from_str(include_str!("../data/taxonomy.json"))
+                .expect("the bundled taxonomy is valid json")
+        })
+    }
+
+    /// Finds a group, ignoring case.
+    pub fn group(&self, name: &str) -> Option<&FoodGroup> {
+        self.groups
+            .iter()
+            .find(|group| same_name(&group.name, name))
+    }
+
+    pub fn icon(&self, group: Option<&str>) -> &str {
+        group
+            .and_then(|name| self.group(name))
+            .map_or(UNKNOWN_ICON, |group| group.icon.as_str())
+    }
+
+    pub fn color(&self, group: Option<&str>) -> &str {
+        group
+            .and_then(|name| self.group(name))
+            .map_or(UNKNOWN_COLOR, |group| group.color.as_str())
+    }
+
+    /// Spells a group and subgroup the way the taxonomy does, e.g.
+    /// `herbs and spices` becomes `Herbs and Spices`. Values the taxonomy
+    /// doesn't know are only trimmed.
+    pub fn canonical_names(&self, group: &str, subgroup: Option<&str>) -> (String, Option<String>) {
+        let known = self.group(group);
+        let subgroup = subgroup.map(|subgroup| {
+            known
+                .and_then(|known| known.subgroup(subgroup))
+                .unwrap_or(subgroup.trim())
+                .to_string()
+        });
+        let group = known.map_or(group.trim(), |known| known.name.as_str());
+        (group.to_string(), subgroup)
+    }
+
+    /// Rewrites the group and subgroup of `ingredient` with [`Taxonomy::canonical_names`].
+    pub fn canonicalize(&self, ingredient: &mut NewIngredient) {
+        let (group, subgroup) =
+            self.canonical_names(&ingredient.food_group, ingredient.food_subgroup.as_deref());
+        ingredient.food_group = group;
+        ingredient.food_subgroup = subgroup;
+    }
+}
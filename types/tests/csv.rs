@@ -0,0 +1,32 @@
+use whats_cooking_types::csv::{parse_csv, write_csv};
+
+fn strings(fields: &[&str]) -> Vec<String> {
+    fields.iter().map(|field| field.to_string()).collect()
+}
+
+#[test]
+fn parses_quoted_fields_and_line_endings() {
+    let text = "\u{feff}name,decription\r\nBasil,\"Sweet, with \"\"anise\"\" notes\"\r\n\r\n\"Star\nanise\",\n";
+    assert_eq!(
+        parse_csv(text),
+        vec![
+            strings(&["name", "decription"]),
+            strings(&["Basil", "Sweet, with \"anise\" notes"]),
+            strings(&["Star\nanise", ""]),
+        ]
+    );
+    assert_eq!(parse_csv("a,b"), vec![strings(&["a", "b"])]);
+    assert!(parse_csv("\n\n").is_empty());
+}
+
+#[test]
+fn writes_what_it_reads() {
+    let records = vec![
+        strings(&["name", "decription"]),
+        strings(&["Basil", "Sweet, with \"anise\" notes"]),
+        strings(&["Star\nanise", ""]),
+    ];
+    let text = write_csv(&records);
+    assert!(text.starts_with("name,decription\nBasil,\"Sweet, with \"\"anise\"\" notes\"\n"));
+    assert_eq!(parse_csv(&text), records);
+}
@@ -0,0 +1,158 @@
+use whats_cooking_types::ingredient_line::{match_catalog, parse_line, ParsedLine};
+use whats_cooking_types::{Ingredient, RecipeIngredient};
+
+fn line(
+    quantity: Option<f64>,
+    quantity_max: Option<f64>,
+    unit: Option<&str>,
+    name: &str,
+    note: Option<&str>,
+) -> ParsedLine {
+    ParsedLine {
+        quantity,
+        quantity_max,
+        unit: unit.map(String::from),
+        name: String::from(name),
+        note: note.map(String::from),
+    }
+}
+
+fn catalog() -> Vec<Ingredient> {
+    [
+        "Onion",
+        "Red onion",
+        "Tomato",
+        "Parsley",
+        "Cheddar cheese",
+        "Salt",
+    ]
+    .iter()
+    .enumerate()
+    .map(|(index, name)| Ingredient {
+        id: index as i32 + 1,
+        name: String::from(*name),
+        name_scientific: None,
+        description: None,
+        food_group: None,
+        food_subgroup: None,
+        foodb_id: None,
+    })
+    .collect()
+}
+
+#[test]
+fn parses_quantities_units_names_and_notes() {
+    assert_eq!(
+        parse_line("2 1/2 cups finely chopped onions"),
+        line(
+            Some(2.5),
+            None,
+            Some("cup"),
+            "onions",
+            Some("finely chopped")
+        )
+    );
+    assert_eq!(
+        parse_line("½ tsp salt"),
+        line(Some(0.5), None, Some("tsp"), "salt", None)
+    );
+    assert_eq!(
+        parse_line("1½ Tablespoons olive oil"),
+        line(Some(1.5), None, Some("tbsp"), "olive oil", None)
+    );
+    assert_eq!(
+        parse_line("200g flour (about 1 2/3 cups), sifted"),
+        line(
+            Some(200.0),
+            None,
+            Some("g"),
+            "flour",
+            Some("sifted, about 1 2/3 cups")
+        )
+    );
+    assert_eq!(
+        parse_line("1,5 kg potatoes, peeled and diced"),
+        line(
+            Some(1.5),
+            None,
+            Some("kg"),
+            "potatoes",
+            Some("peeled and diced")
+        )
+    );
+    assert_eq!(
+        parse_line("8 fl oz milk"),
+        line(Some(8.0), None, Some("fl oz"), "milk", None)
+    );
+}
+
+#[test]
+fn parses_ranges() {
+    let garlic = line(
+        Some(2.0),
+        Some(3.0),
+        Some("clove"),
+        "garlic",
+        Some("minced"),
+    );
+    assert_eq!(parse_line("2-3 cloves garlic, minced"), garlic);
+    assert_eq!(parse_line("2 – 3 cloves garlic, minced"), garlic);
+    assert_eq!(parse_line("2 to 3 cloves of garlic, minced"), garlic);
+    assert_eq!(parse_line("¼–½ tsp chili flakes").quantity_max, Some(0.5));
+}
+
+#[test]
+fn leaves_lines_without_quantities_alone() {
+    assert_eq!(
+        parse_line("Salt and pepper"),
+        line(None, None, None, "Salt and pepper", None)
+    );
+    assert_eq!(
+        parse_line("a pinch of salt"),
+        line(Some(1.0), None, Some("pinch"), "salt", None)
+    );
+    // "large" is the only word left, so it is the name
+    assert_eq!(
+        parse_line("2 large"),
+        line(Some(2.0), None, None, "large", None)
+    );
+    assert_eq!(
+        parse_line("1 cup jelly beans"),
+        line(Some(1.0), None, Some("cup"), "jelly beans", None)
+    );
+}
+
+#[test]
+fn matches_names_to_the_catalog() {
+    let catalog = catalog();
+    let found = |name: &str| match_catalog(name, &catalog).map(|m| (m.ingredient.id, m.confidence));
+
+    assert_eq!(found("onions"), Some((1, 1.0)));
+    assert_eq!(found("Red Onions"), Some((2, 1.0)));
+    assert_eq!(found("tomatoes"), Some((3, 1.0)));
+    let (id, confidence) = found("parsely").unwrap();
+    assert_eq!(id, 4);
+    assert!(confidence > 0.7 && confidence < 1.0);
+    let (id, confidence) = found("cheddar").unwrap();
+    assert_eq!(id, 5);
+    assert!(confidence < 0.9);
+    assert_eq!(found("chocolate"), None);
+    assert_eq!(found(""), None);
+}
+
+#[test]
+fn becomes_a_recipe_ingredient() {
+    let catalog = catalog();
+    let parsed = parse_line("2-3 medium onions, sliced");
+    assert_eq!(
+        parsed.clone().into_recipe_ingredient(Some(&catalog[0])),
+        RecipeIngredient {
+            ingredient_id: Some(1),
+            name: String::from("Onion"),
+            quantity: Some(2.0),
+            unit: None,
+            note: Some(String::from("2 to 3, medium, sliced")),
+        }
+    );
+    assert_eq!(parsed.into_recipe_ingredient(None).name, "onions");
+}
@@ -0,0 +1,90 @@
+use whats_cooking_types::search::{edit_distance, merge_ranges, search};
+use whats_cooking_types::Ingredient;
+
+fn ingredient(id: i32, name: &str, group: &str) -> Ingredient {
+    Ingredient {
+        id,
+        name: String::from(name),
+        name_scientific: None,
+        description: None,
+        food_group: Some(String::from(group)),
+        food_subgroup: None,
+        foodb_id: None,
+    }
+}
+
+fn names(ingredients: &[Ingredient], query: &str) -> Vec<String> {
+    search(ingredients, query)
+        .into_iter()
+        .map(|hit| hit.ingredient.name.clone())
+        .collect()
+}
+
+fn catalog() -> Vec<Ingredient> {
+    let mut basil = ingredient(1, "Sweet basil", "Herbs and Spices");
+    basil.name_scientific = Some(String::from("Ocimum basilicum"));
+    basil.food_subgroup = Some(String::from("Herbs"));
+    let mut potato = ingredient(3, "Potato", "Vegetables");
+    potato.description = Some(String::from("Starchy tuber, great roasted"));
+    vec![
+        basil,
+        ingredient(2, "Tomato", "Vegetables"),
+        potato,
+        ingredient(4, "Cherry tomato", "Vegetables"),
+    ]
+}
+
+#[test]
+fn empty_query_returns_everything_in_order() {
+    assert_eq!(
+        names(&catalog(), "  "),
+        vec!["Sweet basil", "Tomato", "Potato", "Cherry tomato"]
+    );
+}
+
+#[test]
+fn prefers_matches_at_the_start_of_the_name() {
+    assert_eq!(
+        names(&catalog(), "to"),
+        vec!["Tomato", "Cherry tomato", "Potato"]
+    );
+}
+
+#[test]
+fn tolerates_typos() {
+    assert_eq!(names(&catalog(), "basli"), vec!["Sweet basil"]);
+    assert_eq!(names(&catalog(), "tomatp"), vec!["Tomato", "Cherry tomato"]);
+    // short words have to match exactly
+    assert!(names(&catalog(), "tmo").is_empty());
+}
+
+#[test]
+fn every_word_has_to_match_in_some_field() {
+    assert_eq!(names(&catalog(), "herbs ocimum"), vec!["Sweet basil"]);
+    assert_eq!(names(&catalog(), "roasted"), vec!["Potato"]);
+    assert!(names(&catalog(), "roasted tomato").is_empty());
+}
+
+#[test]
+fn highlights_the_matched_text() {
+    let catalog = catalog();
+    let hits = search(&catalog, "cherry TOM");
+    assert_eq!(hits[0].highlights.name, vec![0..6, 7..10]);
+
+    let hits = search(&catalog, "herbs");
+    let basil = &hits[0];
+    assert_eq!(basil.highlights.food_group, vec![0..5]);
+    assert!(basil.highlights.name.is_empty());
+}
+
+#[test]
+fn counts_swapped_letters_as_one_edit() {
+    let chars = |s: &str| s.chars().collect::<Vec<_>>();
+    assert_eq!(edit_distance(&chars("basil"), &chars("basli")), 1);
+    assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+}
+
+#[test]
+fn merges_overlapping_ranges() {
+    assert_eq!(merge_ranges(&[4..6, 0..2, 1..3, 6..8]), vec![0..3, 4..8]);
+}
@@ -0,0 +1,141 @@
+use serde_json::json;
+use whats_cooking_types::{
+    Envelope, Ingredient, IngredientQuery, IngredientSort, NewIngredient, Recipe, RecipeIngredient,
+    SortField,
+};
+
+#[test]
+fn ingredient_description_keeps_its_wire_name() {
+    let ingredient: Ingredient = serde_json::from_value(json!({
+        "id": 1,
+        "name": "Basil",
+        "name_scientific": "Ocimum basilicum",
+        "decription": "Sweet",
+        "food_group": "Herbs and Spices",
+        "food_subgroup": null,
+    }))
+    .unwrap();
+    assert_eq!(ingredient.description.as_deref(), Some("Sweet"));
+
+    let new = NewIngredient {
+        name: String::from("Basil"),
+        food_group: String::from("Herbs and Spices"),
+        description: Some(String::from("Sweet")),
+        ..NewIngredient::default()
+    };
+    assert_eq!(serde_json::to_value(&new).unwrap()["decription"], "Sweet");
+}
+
+#[test]
+fn envelope_wraps_the_payload() {
+    let envelope = Envelope::new(vec![1, 2]);
+    assert_eq!(
+        serde_json::to_value(&envelope).unwrap(),
+        json!({ "response": [1, 2] })
+    );
+}
+
+#[test]
+fn recipes_saved_before_steps_existed_still_load() {
+    let recipe: Recipe = serde_json::from_value(json!({
+        "id": 1,
+        "name": "Pesto",
+        "description": null,
+        "link": null,
+        "url": null,
+    }))
+    .unwrap();
+    assert!(recipe.steps.is_empty());
+    assert!(recipe.ingredients.is_empty());
+    assert_eq!(recipe.total_time(), None);
+}
+
+#[test]
+fn recipe_total_time_does_not_overflow() {
+    let recipe: Recipe = serde_json::from_value(json!({
+        "id": 1,
+        "name": "Pesto",
+        "description": null,
+        "link": null,
+        "url": null,
+        "prep_time": u32::MAX,
+        "cook_time": 10,
+    }))
+    .unwrap();
+    assert_eq!(recipe.total_time(), Some(u32::MAX));
+}
+
+#[test]
+fn recipe_ingredients_read_like_a_recipe_line() {
+    let line = RecipeIngredient {
+        ingredient_id: Some(3),
+        name: String::from("onions"),
+        quantity: Some(2.5),
+        unit: Some(String::from("cups")),
+        note: Some(String::from("finely chopped")),
+    };
+    assert_eq!(line.to_string(), "2.5 cups onions, finely chopped");
+
+    let line = RecipeIngredient {
+        name: String::from("salt"),
+        quantity: Some(1.0),
+        ..RecipeIngredient::default()
+    };
+    assert_eq!(line.to_string(), "1 salt");
+}
+
+#[test]
+fn ingredient_queries_encode_as_query_strings() {
+    assert_eq!(IngredientQuery::default().to_query_string(), "");
+
+    let query = IngredientQuery {
+        q: Some(String::from("sweet basil")),
+        group: Some(String::from("Herbs & Spices")),
+        limit: Some(50),
+        offset: Some(100),
+        sort: Some(IngredientSort {
+            field: SortField::FoodGroup,
+            descending: true,
+        }),
+    };
+    assert_eq!(
+        query.to_query_string(),
+        "?q=sweet%20basil&group=Herbs%20%26%20Spices&limit=50&offset=100&sort=-food_group"
+    );
+}
+
+#[test]
+fn ingredient_sorts_round_trip_through_strings() {
+    let sort: IngredientSort = "-name".parse().unwrap();
+    assert_eq!(sort.field, SortField::Name);
+    assert!(sort.descending);
+    assert_eq!(sort.to_string(), "-name");
+    assert_eq!(json!(sort), json!("-name"));
+    assert!("colour".parse::<IngredientSort>().is_err());
+}
+
+#[test]
+fn sorting_puts_missing_values_last() {
+    let mut plain = Ingredient {
+        id: 1,
+        name: String::from("Water"),
+        name_scientific: None,
+        description: None,
+        food_group: None,
+        food_subgroup: None,
+        foodb_id: None,
+    };
+    let mut fruit = plain.clone();
+    fruit.id = 2;
+    fruit.food_group = Some(String::from("Fruits"));
+    plain.name = String::from("Salt");
+    for descending in [false, true] {
+        let sort = IngredientSort {
+            field: SortField::FoodGroup,
+            descending,
+        };
+        let mut ingredients = [plain.clone(), fruit.clone()];
+        ingredients.sort_by(|a, b| sort.compare(a, b));
+        assert_eq!(ingredients[0].id, 2);
+    }
+}
@@ -0,0 +1,52 @@
+use whats_cooking_types::taxonomy::{Taxonomy, UNKNOWN_COLOR, UNKNOWN_ICON};
+use whats_cooking_types::NewIngredient;
+
+#[test]
+fn bundled_taxonomy_covers_the_foodb_groups() {
+    let taxonomy = Taxonomy::bundled();
+    assert_eq!(taxonomy.groups.len(), 22);
+    for group in &taxonomy.groups {
+        assert!(
+            !group.subgroups.is_empty(),
+            "{} has no subgroups",
+            group.name
+        );
+        assert!(group.color.starts_with('#'), "{} has no colour", group.name);
+    }
+}
+
+#[test]
+fn looks_groups_up_ignoring_case() {
+    let taxonomy = Taxonomy::bundled();
+    assert_eq!(taxonomy.icon(Some("Fruits")), "🍌");
+    assert_eq!(taxonomy.icon(Some(" herbs and spices ")), "🧂");
+    assert_eq!(taxonomy.icon(Some("Rocks")), UNKNOWN_ICON);
+    assert_eq!(taxonomy.icon(None), UNKNOWN_ICON);
+    assert_eq!(taxonomy.color(None), UNKNOWN_COLOR);
+    let group = taxonomy.group("vegetables").unwrap();
+    assert_eq!(group.subgroup("root VEGETABLES"), Some("Root vegetables"));
+}
+
+#[test]
+fn canonicalizes_known_groups_and_keeps_new_ones() {
+    let taxonomy = Taxonomy::bundled();
+    let mut ingredient = NewIngredient {
+        name: String::from("Basil"),
+        food_group: String::from("herbs and spices "),
+        food_subgroup: Some(String::from("herbs")),
+        ..NewIngredient::default()
+    };
+    taxonomy.canonicalize(&mut ingredient);
+    assert_eq!(ingredient.food_group, "Herbs and Spices");
+    assert_eq!(ingredient.food_subgroup.as_deref(), Some("Herbs"));
+
+    let mut ingredient = NewIngredient {
+        name: String::from("Cricket"),
+        food_group: String::from(" Insects"),
+        food_subgroup: Some(String::from("Crickets ")),
+        ..NewIngredient::default()
+    };
+    taxonomy.canonicalize(&mut ingredient);
+    assert_eq!(ingredient.food_group, "Insects");
+    assert_eq!(ingredient.food_subgroup.as_deref(), Some("Crickets"));
+}